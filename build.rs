@@ -11,6 +11,7 @@ fn main() {
 }
 
 fn use_feature_or_nothing(feature: &str) {
+    println!("cargo:rustc-check-cfg=cfg({})", feature);
     if has_feature(feature) {
         use_feature(feature);
     }
@@ -0,0 +1,128 @@
+use crate::{Bufferable, WriteLayered};
+use std::fmt;
+use std::io::{self, Write};
+
+/// The largest prime smaller than 2^16.
+const MOD: u32 = 65521;
+
+/// The largest number of bytes which can be summed before `b` could overflow
+/// a `u32`, so that the modulo can be deferred.
+const NMAX: usize = 5552;
+
+/// Adapts a [`WriteLayered`] to compute an Adler-32 checksum of the data
+/// written to it.
+///
+/// On [`WriteLayered::close`], the checksum is written to the inner stream,
+/// as 4 big-endian bytes, before the inner stream is closed. On
+/// [`Bufferable::abandon`], no checksum is written.
+pub struct Adler32Writer<Inner> {
+    inner: Inner,
+    a: u32,
+    b: u32,
+}
+
+impl<Inner: WriteLayered> Adler32Writer<Inner> {
+    /// Construct a new `Adler32Writer` which wraps `inner`.
+    pub fn new(inner: Inner) -> Self {
+        Self { inner, a: 1, b: 0 }
+    }
+
+    /// Return the Adler-32 checksum of the data written so far.
+    pub fn checksum(&self) -> u32 {
+        (self.b << 16) | self.a
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Bytes written directly to the underlying writer aren't checksummed.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        for chunk in data.chunks(NMAX) {
+            for byte in chunk {
+                self.a += u32::from(*byte);
+                self.b += self.a;
+            }
+            self.a %= MOD;
+            self.b %= MOD;
+        }
+    }
+}
+
+impl<Inner: WriteLayered> WriteLayered for Adler32Writer<Inner> {
+    fn close(&mut self) -> io::Result<()> {
+        self.inner.write_all(&self.checksum().to_be_bytes())?;
+        self.inner.close()
+    }
+}
+
+impl<Inner: WriteLayered> Bufferable for Adler32Writer<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: WriteLayered> Write for Adler32Writer<Inner> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let size = self.inner.write(buf)?;
+        self.update(&buf[..size]);
+        Ok(size)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for Adler32Writer<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("Adler32Writer");
+        b.field("inner", &self.inner);
+        b.field(
+            "checksum",
+            &format_args!("{:#010x}", (self.b << 16) | self.a),
+        );
+        b.finish()
+    }
+}
+
+#[test]
+fn test_adler32_writer() {
+    let mut writer = Adler32Writer::new(io::Cursor::new(Vec::new()));
+    writer.write_all(b"Wiki").unwrap();
+    writer.write_all(b"pedia").unwrap();
+    assert_eq!(writer.checksum(), 0x11e6_0398);
+    writer.close().unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"Wikipedia\x11\xe6\x03\x98");
+}
+
+#[test]
+fn test_adler32_writer_long() {
+    // Exercise the deferred modulo with more than `NMAX` bytes.
+    let data = vec![0xff; 3 * NMAX + 7];
+    let mut writer = Adler32Writer::new(io::Cursor::new(Vec::new()));
+    writer.write_all(&data).unwrap();
+    let (mut a, mut b) = (1_u32, 0_u32);
+    for byte in &data {
+        a = (a + u32::from(*byte)) % MOD;
+        b = (b + a) % MOD;
+    }
+    assert_eq!(writer.checksum(), (b << 16) | a);
+    writer.abandon();
+    assert_eq!(writer.get_ref().get_ref().len(), data.len());
+}
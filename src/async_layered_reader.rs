@@ -0,0 +1,233 @@
+use crate::{default_poll_read, default_poll_read_vectored, AsyncReadLayered, Bufferable, Status};
+use futures_io::AsyncRead;
+use std::fmt;
+use std::io::{self, IoSliceMut};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Adapts an `AsyncRead` to implement [`AsyncReadLayered`].
+///
+/// This is the async counterpart of [`LayeredReader`], and maps the inner
+/// stream's reads to statuses in the same way.
+///
+/// [`LayeredReader`]: crate::LayeredReader
+pub struct AsyncLayeredReader<Inner> {
+    inner: Option<Inner>,
+    eos_as_push: bool,
+    line_by_line: bool,
+}
+
+impl<Inner: AsyncRead + Unpin> AsyncLayeredReader<Inner> {
+    /// Construct a new `AsyncLayeredReader` which wraps `inner` with default
+    /// settings.
+    pub fn new(inner: Inner) -> Self {
+        Self {
+            inner: Some(inner),
+            eos_as_push: false,
+            line_by_line: false,
+        }
+    }
+
+    /// Construct a new `AsyncLayeredReader` which wraps `inner`. When `inner`
+    /// reports end of stream (by returning 0), report a push but keep the
+    /// stream open and continue to read data on it.
+    pub fn with_eos_as_push(inner: Inner) -> Self {
+        Self {
+            inner: Some(inner),
+            eos_as_push: true,
+            line_by_line: false,
+        }
+    }
+
+    /// Construct a new `AsyncLayeredReader` which wraps an `inner` which
+    /// reads its input line-by-line, such as stdin on a terminal.
+    pub fn line_by_line(inner: Inner) -> Self {
+        Self {
+            inner: Some(inner),
+            eos_as_push: false,
+            line_by_line: true,
+        }
+    }
+
+    /// Gets a reference to the underlying reader.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stream has ended or been abandoned.
+    pub fn get_ref(&self) -> &Inner {
+        self.inner
+            .as_ref()
+            .expect("get_ref() called on closed AsyncLayeredReader")
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Reading directly from the underlying reader bypasses this
+    /// `AsyncLayeredReader`'s status tracking.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stream has ended or been abandoned.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        self.inner
+            .as_mut()
+            .expect("get_mut() called on closed AsyncLayeredReader")
+    }
+
+    /// Consume this `AsyncLayeredReader` and return the inner stream.
+    pub fn abandon_into_inner(self) -> Option<Inner> {
+        self.inner
+    }
+
+    /// Translate the result of a read from the inner stream into a status.
+    /// `last_byte` returns the last byte read, given the size of the read.
+    fn status_for(
+        &mut self,
+        result: io::Result<usize>,
+        empty: bool,
+        last_byte: impl FnOnce(usize) -> Option<u8>,
+    ) -> io::Result<(usize, Status)> {
+        match result {
+            Ok(0) if !empty => {
+                if self.eos_as_push {
+                    Ok((0, Status::push()))
+                } else {
+                    self.inner = None;
+                    Ok((0, Status::End))
+                }
+            }
+            Ok(size) => {
+                if self.line_by_line && last_byte(size) == Some(b'\n') {
+                    Ok((size, Status::push()))
+                } else {
+                    Ok((size, Status::active()))
+                }
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => Ok((0, Status::active())),
+            Err(e) => {
+                self.abandon();
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<Inner: AsyncRead + Unpin> AsyncReadLayered for AsyncLayeredReader<Inner> {
+    fn poll_read_with_status(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<(usize, Status)>> {
+        let inner = match self.inner.as_mut() {
+            Some(inner) => inner,
+            None => return Poll::Ready(Ok((0, Status::End))),
+        };
+        let result = match Pin::new(inner).poll_read(cx, buf) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+        let empty = buf.is_empty();
+        Poll::Ready(self.status_for(result, empty, |size| Some(buf[size - 1])))
+    }
+
+    fn poll_read_vectored_with_status(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<io::Result<(usize, Status)>> {
+        let inner = match self.inner.as_mut() {
+            Some(inner) => inner,
+            None => return Poll::Ready(Ok((0, Status::End))),
+        };
+        let result = match Pin::new(inner).poll_read_vectored(cx, bufs) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+        let empty = bufs.iter().all(|b| b.is_empty());
+        Poll::Ready(self.status_for(result, empty, |mut size| {
+            for buf in bufs.iter() {
+                if size <= buf.len() {
+                    return Some(buf[size - 1]);
+                }
+                size -= buf.len();
+            }
+            None
+        }))
+    }
+}
+
+impl<Inner> Bufferable for AsyncLayeredReader<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner = None;
+    }
+}
+
+impl<Inner: AsyncRead + Unpin> AsyncRead for AsyncLayeredReader<Inner> {
+    #[inline]
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        default_poll_read(self, cx, buf)
+    }
+
+    #[inline]
+    fn poll_read_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<io::Result<usize>> {
+        default_poll_read_vectored(self, cx, bufs)
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for AsyncLayeredReader<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("AsyncLayeredReader");
+        b.field("inner", &self.inner);
+        b.field("eos_as_push", &self.eos_as_push);
+        b.field("line_by_line", &self.line_by_line);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_async_layered_reader() {
+    use std::sync::Arc;
+    use std::task::{Wake, Waker};
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    // `&[u8]` reads are always ready, so this doesn't need an executor.
+    fn poll(reader: &mut AsyncLayeredReader<&[u8]>, buf: &mut [u8]) -> (usize, Status) {
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut cx = Context::from_waker(&waker);
+        match Pin::new(reader).poll_read_with_status(&mut cx, buf) {
+            Poll::Ready(result) => result.unwrap(),
+            Poll::Pending => panic!("read from a slice is pending"),
+        }
+    }
+
+    let mut buf = [0; 8];
+    let mut reader = AsyncLayeredReader::new(&b"hello"[..]);
+    assert_eq!(poll(&mut reader, &mut buf), (5, Status::active()));
+    assert_eq!(&buf[..5], b"hello");
+    assert_eq!(poll(&mut reader, &mut buf), (0, Status::End));
+    assert_eq!(poll(&mut reader, &mut buf), (0, Status::End));
+
+    let mut reader = AsyncLayeredReader::with_eos_as_push(&b"hello"[..]);
+    assert_eq!(poll(&mut reader, &mut buf), (5, Status::active()));
+    assert_eq!(poll(&mut reader, &mut buf), (0, Status::push()));
+    assert_eq!(poll(&mut reader, &mut buf), (0, Status::push()));
+
+    let mut reader = AsyncLayeredReader::line_by_line(&b"one\ntwo"[..]);
+    assert_eq!(poll(&mut reader, &mut buf[..4]), (4, Status::push()));
+    assert_eq!(poll(&mut reader, &mut buf), (3, Status::active()));
+    assert_eq!(poll(&mut reader, &mut buf), (0, Status::End));
+}
@@ -1,4 +1,5 @@
 use super::{Bufferable, Status};
+use crate::read_layered::is_retry;
 use crate::to_std_io_read_result;
 use futures_io::AsyncRead;
 use std::io::{self, IoSliceMut};
@@ -61,16 +62,52 @@ pub fn default_poll_read_vectored<Inner: AsyncReadLayered + ?Sized>(
 /// Default implementation of
 /// [`AsyncReadLayered::poll_read_vectored_with_status`] in terms of
 /// [`AsyncReadLayered::poll_read_with_status`].
+///
+/// Like the synchronous default of [`ReadLayered::read_vectored_with_status`],
+/// this reads into each non-empty buffer in turn, with one read per buffer,
+/// stopping after a read which doesn't fill its buffer or which reports a
+/// push or end of stream. If a read is pending after some data has been
+/// read, the data is returned, with `Status::active()`. If a read fails after
+/// some data has been read, the error is returned, unless it's `Interrupted`
+/// or `WouldBlock`, which only ask the caller to retry; in that case, the
+/// data is returned as if the read were pending.
+///
+/// [`ReadLayered::read_vectored_with_status`]: crate::ReadLayered::read_vectored_with_status
 pub fn default_poll_read_vectored_with_status<Inner: AsyncReadLayered + ?Sized>(
-    inner: Pin<&mut Inner>,
+    mut inner: Pin<&mut Inner>,
     cx: &mut Context<'_>,
     bufs: &mut [IoSliceMut<'_>],
 ) -> Poll<io::Result<(usize, Status)>> {
-    let buf = bufs
-        .iter_mut()
-        .find(|b| !b.is_empty())
-        .map_or(&mut [][..], |b| &mut **b);
-    inner.poll_read_with_status(cx, buf)
+    let mut nonempty = bufs.iter_mut().filter(|b| !b.is_empty());
+    let first = match nonempty.next() {
+        Some(first) => first,
+        None => return inner.poll_read_with_status(cx, &mut []),
+    };
+
+    let (mut total, mut status) = match inner.as_mut().poll_read_with_status(cx, first) {
+        Poll::Ready(Ok(size_and_status)) => size_and_status,
+        other => return other,
+    };
+    if total != first.len() || status != Status::active() {
+        return Poll::Ready(Ok((total, status)));
+    }
+    for buf in nonempty {
+        let size = match inner.as_mut().poll_read_with_status(cx, buf) {
+            Poll::Ready(Ok((size, buf_status))) => {
+                status = buf_status;
+                size
+            }
+            Poll::Ready(Err(e)) if !is_retry(&e) => return Poll::Ready(Err(e)),
+            Poll::Ready(Err(_)) | Poll::Pending => {
+                return Poll::Ready(Ok((total, Status::active())))
+            }
+        };
+        total += size;
+        if size != buf.len() || status != Status::active() {
+            break;
+        }
+    }
+    Poll::Ready(Ok((total, status)))
 }
 
 impl<R: AsyncReadLayered + Unpin> AsyncReadLayered for Box<R> {
@@ -122,3 +159,120 @@ impl<R: AsyncReadLayered + Unpin> AsyncReadLayered for &mut R {
         (**self).minimum_buffer_size()
     }
 }
+
+#[test]
+fn test_default_poll_read_vectored_with_status() {
+    use std::sync::Arc;
+    use std::task::{Wake, Waker};
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    // A reader which is pending on the read numbered `pending_at`, and fails
+    // on the read numbered `fails_at`.
+    struct MockReader {
+        data: &'static [u8],
+        reads: usize,
+        pending_at: usize,
+        fails_at: usize,
+    }
+
+    impl AsyncReadLayered for MockReader {
+        fn poll_read_with_status(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<(usize, Status)>> {
+            self.reads += 1;
+            if self.reads == self.pending_at {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            if self.reads == self.fails_at {
+                return Poll::Ready(Err(io::Error::new(io::ErrorKind::Other, "read failed")));
+            }
+            let size = buf.len().min(self.data.len());
+            buf[..size].copy_from_slice(&self.data[..size]);
+            self.data = &self.data[size..];
+            let status = if self.data.is_empty() {
+                Status::End
+            } else {
+                Status::active()
+            };
+            Poll::Ready(Ok((size, status)))
+        }
+    }
+
+    impl Bufferable for MockReader {
+        fn abandon(&mut self) {
+            self.data = &[];
+        }
+    }
+
+    impl AsyncRead for MockReader {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            default_poll_read(self, cx, buf)
+        }
+    }
+
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut cx = Context::from_waker(&waker);
+    let (mut a, mut b, mut c) = ([0; 3], [0; 3], [0; 4]);
+
+    // Every buffer is filled in a single poll.
+    let mut reader = MockReader {
+        data: b"abcdefgh",
+        reads: 0,
+        pending_at: 0,
+        fails_at: 0,
+    };
+    let mut bufs = [
+        IoSliceMut::new(&mut a),
+        IoSliceMut::new(&mut []),
+        IoSliceMut::new(&mut b),
+        IoSliceMut::new(&mut c),
+    ];
+    let result = Pin::new(&mut reader).poll_read_vectored_with_status(&mut cx, &mut bufs);
+    assert!(matches!(result, Poll::Ready(Ok((8, Status::End)))));
+    assert_eq!((&a, &b, &c[..2]), (b"abc", b"def", &b"gh"[..]));
+
+    // A pending read stops the fill, with what's been read so far.
+    let mut reader = MockReader {
+        data: b"abcdefgh",
+        reads: 0,
+        pending_at: 2,
+        fails_at: 0,
+    };
+    let mut bufs = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)];
+    let result = Pin::new(&mut reader).poll_read_vectored_with_status(&mut cx, &mut bufs);
+    assert!(matches!(result, Poll::Ready(Ok((3, Status::Open(_))))));
+
+    // A pending first read is pending.
+    let mut reader = MockReader {
+        data: b"abcdefgh",
+        reads: 0,
+        pending_at: 1,
+        fails_at: 0,
+    };
+    let mut bufs = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)];
+    let result = Pin::new(&mut reader).poll_read_vectored_with_status(&mut cx, &mut bufs);
+    assert!(result.is_pending());
+
+    // A failed read after some data has been read reports the error.
+    let mut reader = MockReader {
+        data: b"abcdefgh",
+        reads: 0,
+        pending_at: 0,
+        fails_at: 2,
+    };
+    let mut bufs = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)];
+    let result = Pin::new(&mut reader).poll_read_vectored_with_status(&mut cx, &mut bufs);
+    assert!(matches!(result, Poll::Ready(Err(ref e)) if e.kind() == io::ErrorKind::Other));
+}
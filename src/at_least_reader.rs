@@ -0,0 +1,187 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    ReadLayered, Status,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Adapts a [`ReadLayered`] with a `minimum_buffer_size` so that it can be
+/// read with buffers of any size.
+///
+/// Reads into buffers at least as large as the inner stream's
+/// `minimum_buffer_size` are passed through directly. Smaller reads are
+/// served from an internal buffer of `minimum_buffer_size` bytes, which is
+/// refilled from the inner stream once it has been drained. The status of
+/// each inner read is reported on the read which drains its data.
+///
+/// The `minimum_buffer_size` of an `AtLeastReader` is 0.
+pub struct AtLeastReader<Inner> {
+    inner: Inner,
+    buf: Vec<u8>,
+    pos: usize,
+    status: Status,
+}
+
+impl<Inner: ReadLayered> AtLeastReader<Inner> {
+    /// Construct a new `AtLeastReader` which wraps `inner`.
+    pub fn new(inner: Inner) -> Self {
+        Self {
+            inner,
+            buf: Vec::new(),
+            pos: 0,
+            status: Status::active(),
+        }
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Consume this `AtLeastReader` and return the inner stream.
+    ///
+    /// Any buffered input which hasn't been returned yet is discarded.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+
+    /// Read from the inner stream into the internal buffer.
+    fn fill(&mut self, minimum: usize) -> io::Result<()> {
+        self.buf.resize(minimum, 0);
+        self.pos = 0;
+        let result = self.inner.read_with_status(&mut self.buf);
+        let (size, status) = match result {
+            Ok(size_and_status) => size_and_status,
+            Err(e) => {
+                self.buf.clear();
+                return Err(e);
+            }
+        };
+        self.buf.truncate(size);
+        self.status = status;
+        Ok(())
+    }
+}
+
+impl<Inner: ReadLayered> ReadLayered for AtLeastReader<Inner> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        if self.pos == self.buf.len() {
+            if self.status.is_end() {
+                return Ok((0, Status::End));
+            }
+            let minimum = self.inner.minimum_buffer_size();
+            if buf.len() >= minimum {
+                let (size, status) = self.inner.read_with_status(buf)?;
+                self.status = status;
+                return Ok((size, status));
+            }
+            self.fill(minimum)?;
+        }
+
+        let size = (self.buf.len() - self.pos).min(buf.len());
+        buf[..size].copy_from_slice(&self.buf[self.pos..self.pos + size]);
+        self.pos += size;
+        if self.pos == self.buf.len() {
+            Ok((size, self.status))
+        } else {
+            Ok((size, Status::active()))
+        }
+    }
+}
+
+impl<Inner: ReadLayered> Bufferable for AtLeastReader<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon();
+        self.buf.clear();
+        self.pos = 0;
+        self.status = Status::End;
+    }
+}
+
+impl<Inner: ReadLayered> Read for AtLeastReader<Inner> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for AtLeastReader<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("AtLeastReader");
+        b.field("inner", &self.inner);
+        b.field("buffered", &(self.buf.len() - self.pos));
+        b.finish()
+    }
+}
+
+#[test]
+fn test_at_least_reader() {
+    use crate::SliceReader;
+
+    // A reader which insists on reading whole blocks.
+    struct BlockReader<'a>(SliceReader<'a>);
+
+    impl ReadLayered for BlockReader<'_> {
+        fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+            if buf.len() < 4 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "buffer too small",
+                ));
+            }
+            self.0.read_with_status(buf)
+        }
+
+        fn minimum_buffer_size(&self) -> usize {
+            4
+        }
+    }
+
+    impl Bufferable for BlockReader<'_> {
+        fn abandon(&mut self) {
+            self.0.abandon()
+        }
+    }
+
+    impl Read for BlockReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            default_read(self, buf)
+        }
+    }
+
+    let mut buf = [0; 1];
+    let mut inner = BlockReader(SliceReader::new(b"hello"));
+    assert_eq!(
+        inner.read_with_status(&mut buf).unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+
+    let mut reader = AtLeastReader::new(inner);
+    assert_eq!(reader.minimum_buffer_size(), 0);
+    let mut v = Vec::new();
+    loop {
+        let (size, status) = reader.read_with_status(&mut buf).unwrap();
+        v.extend_from_slice(&buf[..size]);
+        if status.is_end() {
+            break;
+        }
+        assert_eq!(size, 1);
+    }
+    assert_eq!(v, b"hello");
+}
@@ -0,0 +1,377 @@
+#[cfg(feature = "flate2")]
+use crate::GzDecodeReader;
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    ReadLayered, Status,
+};
+#[cfg(feature = "ruzstd")]
+use ruzstd::{BlockDecodingStrategy, FrameDecoder};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+#[cfg(feature = "flate2")]
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+#[cfg(feature = "ruzstd")]
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+/// Adapts a [`ReadLayered`] to decompress its data if it's compressed, and
+/// otherwise return it unchanged.
+///
+/// On the first read, the beginning of the stream is read and checked for
+/// the magic number of a gzip stream, with the `flate2` feature, or of a
+/// zstd frame, with the `ruzstd` feature. The bytes read to check are held
+/// back and then read again by the chosen path. Input in a format whose
+/// feature isn't enabled is returned unchanged.
+///
+/// When the input is compressed, `Status::End` is reported at the end of the
+/// compressed data, and pushes from the inner stream aren't forwarded, as
+/// with [`GzDecodeReader`]. When it isn't, statuses from the inner stream
+/// are returned unchanged.
+///
+/// [`GzDecodeReader`]: crate::GzDecodeReader
+pub struct AutoDecompressReader<Inner> {
+    state: State<Inner>,
+}
+
+enum State<Inner> {
+    Sniffing(Holdback<Inner>),
+    Raw(Holdback<Inner>),
+    #[cfg(feature = "flate2")]
+    Gzip(GzDecodeReader<Holdback<Inner>>),
+    #[cfg(feature = "ruzstd")]
+    Zstd(Holdback<Inner>, Box<FrameDecoder>),
+    Abandoned,
+}
+
+impl<Inner: ReadLayered> AutoDecompressReader<Inner> {
+    /// Construct a new `AutoDecompressReader` which wraps `inner`.
+    pub fn new(inner: Inner) -> Self {
+        Self {
+            state: State::Sniffing(Holdback {
+                inner,
+                held: Vec::new(),
+                pos: 0,
+                held_status: Status::active(),
+            }),
+        }
+    }
+
+    /// Return `true` if the input was found to be compressed, or `None` if
+    /// that hasn't been determined yet.
+    pub fn is_compressed(&self) -> Option<bool> {
+        match &self.state {
+            State::Sniffing(_) | State::Abandoned => None,
+            State::Raw(_) => Some(false),
+            #[cfg(feature = "flate2")]
+            State::Gzip(_) => Some(true),
+            #[cfg(feature = "ruzstd")]
+            State::Zstd(..) => Some(true),
+        }
+    }
+
+    /// Read the beginning of the stream and choose how to read it.
+    fn sniff(&mut self) -> io::Result<()> {
+        let holdback = match &mut self.state {
+            State::Sniffing(holdback) => holdback,
+            _ => return Ok(()),
+        };
+        let format = loop {
+            if let Some(format) = Format::detect(&holdback.held, holdback.held_status.is_end()) {
+                break format;
+            }
+            let start = holdback.held.len();
+            holdback.held.resize(start + 4, 0);
+            let result = holdback.inner.read_with_status(&mut holdback.held[start..]);
+            let (size, status) = match result {
+                Ok(size_and_status) => size_and_status,
+                Err(e) => {
+                    holdback.held.truncate(start);
+                    return Err(e);
+                }
+            };
+            holdback.held.truncate(start + size);
+            holdback.held_status = status;
+        };
+
+        let holdback = match std::mem::replace(&mut self.state, State::Abandoned) {
+            State::Sniffing(holdback) => holdback,
+            _ => unreachable!(),
+        };
+        self.state = match format {
+            Format::Raw => State::Raw(holdback),
+            #[cfg(feature = "flate2")]
+            Format::Gzip => State::Gzip(GzDecodeReader::new(holdback)),
+            #[cfg(feature = "ruzstd")]
+            Format::Zstd => {
+                let mut holdback = holdback;
+                let mut decoder = Box::new(FrameDecoder::new());
+                if let Err(e) = decoder.init(&mut holdback) {
+                    holdback.abandon();
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, e));
+                }
+                State::Zstd(holdback, decoder)
+            }
+        };
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    Raw,
+    #[cfg(feature = "flate2")]
+    Gzip,
+    #[cfg(feature = "ruzstd")]
+    Zstd,
+}
+
+impl Format {
+    /// Determine the format from the beginning of the stream, or return
+    /// `None` if more input is needed.
+    fn detect(prefix: &[u8], ended: bool) -> Option<Self> {
+        let candidates: &[(&[u8], Self)] = &[
+            #[cfg(feature = "flate2")]
+            (GZIP_MAGIC, Self::Gzip),
+            #[cfg(feature = "ruzstd")]
+            (ZSTD_MAGIC, Self::Zstd),
+        ];
+        let mut incomplete = false;
+        for (magic, format) in candidates {
+            if prefix.starts_with(magic) {
+                return Some(*format);
+            }
+            incomplete |= magic.starts_with(prefix);
+        }
+        if incomplete && !ended {
+            None
+        } else {
+            Some(Self::Raw)
+        }
+    }
+}
+
+impl<Inner: ReadLayered> ReadLayered for AutoDecompressReader<Inner> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        self.sniff()?;
+        match &mut self.state {
+            State::Raw(holdback) => holdback.read_with_status(buf),
+            #[cfg(feature = "flate2")]
+            State::Gzip(decoder) => decoder.read_with_status(buf),
+            #[cfg(feature = "ruzstd")]
+            State::Zstd(holdback, decoder) => {
+                // Decode until enough output can be collected, as
+                // `ruzstd::StreamingDecoder` does.
+                while decoder.can_collect() < buf.len() && !decoder.is_finished() {
+                    let needed = buf.len() - decoder.can_collect();
+                    decoder
+                        .decode_blocks(&mut *holdback, BlockDecodingStrategy::UptoBytes(needed))
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                }
+                let size = decoder.read(buf)?;
+                if decoder.is_finished() && decoder.can_collect() == 0 {
+                    Ok((size, Status::End))
+                } else {
+                    Ok((size, Status::active()))
+                }
+            }
+            State::Abandoned => Ok((0, Status::End)),
+            State::Sniffing(_) => unreachable!(),
+        }
+    }
+
+    #[inline]
+    fn minimum_buffer_size(&self) -> usize {
+        match &self.state {
+            State::Sniffing(holdback) | State::Raw(holdback) => holdback.minimum_buffer_size(),
+            _ => 0,
+        }
+    }
+}
+
+impl<Inner: ReadLayered> Bufferable for AutoDecompressReader<Inner> {
+    fn abandon(&mut self) {
+        match &mut self.state {
+            State::Sniffing(holdback) | State::Raw(holdback) => holdback.abandon(),
+            #[cfg(feature = "flate2")]
+            State::Gzip(decoder) => decoder.abandon(),
+            #[cfg(feature = "ruzstd")]
+            State::Zstd(holdback, _) => holdback.abandon(),
+            State::Abandoned => (),
+        }
+        self.state = State::Abandoned;
+    }
+}
+
+impl<Inner: ReadLayered> Read for AutoDecompressReader<Inner> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for AutoDecompressReader<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("AutoDecompressReader");
+        match &self.state {
+            State::Sniffing(holdback) | State::Raw(holdback) => b.field("inner", &holdback.inner),
+            #[cfg(feature = "flate2")]
+            State::Gzip(decoder) => b.field("inner", decoder),
+            #[cfg(feature = "ruzstd")]
+            State::Zstd(holdback, _) => b.field("inner", &holdback.inner),
+            State::Abandoned => &mut b,
+        };
+        b.finish()
+    }
+}
+
+/// A [`ReadLayered`] which returns held-back data and then continues with
+/// the inner stream.
+struct Holdback<Inner> {
+    inner: Inner,
+    held: Vec<u8>,
+    pos: usize,
+    /// The status of the last read into `held`.
+    held_status: Status,
+}
+
+impl<Inner: ReadLayered> ReadLayered for Holdback<Inner> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        if self.pos == self.held.len() {
+            if self.held_status.is_end() {
+                return Ok((0, Status::End));
+            }
+            return self.inner.read_with_status(buf);
+        }
+        let size = (self.held.len() - self.pos).min(buf.len());
+        buf[..size].copy_from_slice(&self.held[self.pos..self.pos + size]);
+        self.pos += size;
+        if self.pos == self.held.len() {
+            Ok((size, self.held_status))
+        } else {
+            Ok((size, Status::active()))
+        }
+    }
+
+    #[inline]
+    fn minimum_buffer_size(&self) -> usize {
+        self.inner.minimum_buffer_size()
+    }
+}
+
+impl<Inner: ReadLayered> Bufferable for Holdback<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon();
+        self.held.clear();
+        self.pos = 0;
+        self.held_status = Status::End;
+    }
+}
+
+impl<Inner: ReadLayered> Read for Holdback<Inner> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for Holdback<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("Holdback");
+        b.field("inner", &self.inner);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_auto_decompress_reader_plain() {
+    use crate::{IntervalPushReader, SliceReader};
+
+    // Plain input keeps its pushes, including within the held-back bytes.
+    let inner = IntervalPushReader::new(SliceReader::new(b"plain text"), 3);
+    let mut reader = AutoDecompressReader::new(inner);
+    let mut buf = [0; 16];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (3, Status::push())
+    );
+    assert_eq!(reader.is_compressed(), Some(false));
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "in text");
+
+    // Input which is shorter than any magic number.
+    let mut reader = AutoDecompressReader::new(SliceReader::new(&[0x1f]));
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data).unwrap();
+    assert_eq!(data, [0x1f]);
+}
+
+#[cfg(feature = "flate2")]
+#[test]
+fn test_auto_decompress_reader_gzip() {
+    use crate::{IntervalPushReader, SliceReader};
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(b"hello, gzip world").unwrap();
+    let compressed = encoder.finish().unwrap();
+
+    // Deliver the magic number in pieces.
+    let inner = IntervalPushReader::new(SliceReader::new(&compressed), 1);
+    let mut reader = AutoDecompressReader::new(inner);
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "hello, gzip world");
+    assert_eq!(reader.is_compressed(), Some(true));
+}
+
+#[cfg(feature = "ruzstd")]
+#[test]
+fn test_auto_decompress_reader_zstd() {
+    use crate::SliceReader;
+
+    // `printf 'hello, zstd world' | zstd`
+    let compressed = [
+        0x28, 0xb5, 0x2f, 0xfd, 0x04, 0x58, 0x89, 0x00, 0x00, 0x68, 0x65, 0x6c, 0x6c, 0x6f, 0x2c,
+        0x20, 0x7a, 0x73, 0x74, 0x64, 0x20, 0x77, 0x6f, 0x72, 0x6c, 0x64, 0x70, 0x9a, 0x24, 0x1c,
+    ];
+    let mut reader = AutoDecompressReader::new(SliceReader::new(&compressed));
+    let mut buf = [0; 64];
+    let (size, status) = reader.read_with_status(&mut buf).unwrap();
+    assert_eq!(&buf[..size], b"hello, zstd world");
+    assert_eq!(status, Status::End);
+    assert_eq!(reader.is_compressed(), Some(true));
+}
@@ -0,0 +1,120 @@
+use crate::{Bufferable, WriteLayered};
+use std::fmt;
+use std::io::{self, Write};
+
+/// Adapts a [`WriteLayered`] to flush after every write.
+///
+/// This is like [`std::io::LineWriter`], but flushes unconditionally, which
+/// avoids forgotten flushes on interactive streams such as sockets.
+pub struct AutoFlushWriter<Inner> {
+    inner: Inner,
+}
+
+impl<Inner: WriteLayered> AutoFlushWriter<Inner> {
+    /// Construct a new `AutoFlushWriter` which wraps `inner`.
+    pub fn new(inner: Inner) -> Self {
+        Self { inner }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Writes made directly to the underlying writer aren't flushed.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `AutoFlushWriter` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+}
+
+impl<Inner: WriteLayered> WriteLayered for AutoFlushWriter<Inner> {
+    #[inline]
+    fn close(&mut self) -> io::Result<()> {
+        self.inner.close()
+    }
+}
+
+impl<Inner: WriteLayered> Bufferable for AutoFlushWriter<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: WriteLayered> Write for AutoFlushWriter<Inner> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let size = self.inner.write(buf)?;
+        self.inner.flush()?;
+        Ok(size)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for AutoFlushWriter<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("AutoFlushWriter");
+        b.field("inner", &self.inner);
+        b.finish()
+    }
+}
+
+/// A sink which records the amount of data written at each flush.
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct FlushCounter {
+    pub(crate) data: Vec<u8>,
+    pub(crate) flushes: Vec<usize>,
+}
+
+#[cfg(test)]
+impl Write for FlushCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flushes.push(self.data.len());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+impl Bufferable for FlushCounter {
+    fn abandon(&mut self) {}
+}
+
+#[cfg(test)]
+impl WriteLayered for FlushCounter {
+    fn close(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_auto_flush_writer() {
+    let mut writer = AutoFlushWriter::new(FlushCounter::default());
+    writer.write_all(b"hello").unwrap();
+    writer.write_all(b" ").unwrap();
+    writer.write_all(b"world").unwrap();
+    writer.close().unwrap();
+    assert_eq!(writer.get_ref().data, b"hello world");
+    assert_eq!(writer.get_ref().flushes, [5, 6, 11]);
+}
@@ -0,0 +1,204 @@
+use crate::{Bufferable, Status, WriteLayered};
+use std::fmt;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+/// A source of the current time for [`AutoPushWriter`] and
+/// [`ThrottledWriter`].
+///
+/// This is implemented for closures returning an `Instant`, such as
+/// [`Instant::now`], so that a mock clock can be used for testing.
+///
+/// [`ThrottledWriter`]: crate::ThrottledWriter
+pub trait Clock {
+    /// Return the current time.
+    fn now(&mut self) -> Instant;
+}
+
+impl<F: FnMut() -> Instant> Clock for F {
+    #[inline]
+    fn now(&mut self) -> Instant {
+        self()
+    }
+}
+
+/// Adapts a [`WriteLayered`] to push data which has been left idle.
+///
+/// When a write arrives, or an active flush is requested, and at least the
+/// idle threshold has passed since the last write, the data written so far
+/// is pushed, with `flush_with_status(Status::push())`, first. This is a
+/// cooperative timer driven by calls on the writer; there's no background
+/// thread, so data which is followed by no further calls isn't pushed.
+pub struct AutoPushWriter<Inner, C> {
+    inner: Inner,
+    clock: C,
+    idle_threshold: Duration,
+    last_write: Option<Instant>,
+}
+
+impl<Inner: WriteLayered> AutoPushWriter<Inner, fn() -> Instant> {
+    /// Construct a new `AutoPushWriter` which wraps `inner` and pushes data
+    /// which has been idle for `idle_threshold`, using [`Instant::now`] as
+    /// its clock.
+    pub fn new(inner: Inner, idle_threshold: Duration) -> Self {
+        Self::with_clock(inner, idle_threshold, Instant::now)
+    }
+}
+
+impl<Inner: WriteLayered, C: Clock> AutoPushWriter<Inner, C> {
+    /// Construct a new `AutoPushWriter` which wraps `inner` and pushes data
+    /// which has been idle for `idle_threshold`, according to `clock`.
+    pub fn with_clock(inner: Inner, idle_threshold: Duration, clock: C) -> Self {
+        Self {
+            inner,
+            clock,
+            idle_threshold,
+            last_write: None,
+        }
+    }
+
+    /// Return the duration after which idle data is pushed.
+    pub fn idle_threshold(&self) -> Duration {
+        self.idle_threshold
+    }
+
+    /// Set the duration after which idle data is pushed.
+    pub fn set_idle_threshold(&mut self, idle_threshold: Duration) {
+        self.idle_threshold = idle_threshold;
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `AutoPushWriter` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+
+    /// Push the data written so far if it has been idle for the threshold,
+    /// and return the current time.
+    fn push_if_idle(&mut self) -> io::Result<Instant> {
+        let now = self.clock.now();
+        if let Some(last_write) = self.last_write {
+            let idle = now.checked_duration_since(last_write).unwrap_or_default();
+            if idle >= self.idle_threshold {
+                self.inner.flush_with_status(Status::push())?;
+                self.last_write = None;
+            }
+        }
+        Ok(now)
+    }
+}
+
+impl<Inner: WriteLayered, C: Clock> WriteLayered for AutoPushWriter<Inner, C> {
+    #[inline]
+    fn close(&mut self) -> io::Result<()> {
+        self.last_write = None;
+        self.inner.close()
+    }
+
+    fn flush_with_status(&mut self, status: Status) -> io::Result<()> {
+        match status {
+            Status::End => self.close(),
+            status if status.is_push() => self.flush(),
+            _ => self.push_if_idle().map(|_now| ()),
+        }
+    }
+}
+
+impl<Inner: WriteLayered, C> Bufferable for AutoPushWriter<Inner, C> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.last_write = None;
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: WriteLayered, C: Clock> Write for AutoPushWriter<Inner, C> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let now = self.push_if_idle()?;
+        let size = self.inner.write(buf)?;
+        if size != 0 {
+            self.last_write = Some(now);
+        }
+        Ok(size)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.last_write = None;
+        self.inner.flush()
+    }
+}
+
+impl<Inner: fmt::Debug, C> fmt::Debug for AutoPushWriter<Inner, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("AutoPushWriter");
+        b.field("inner", &self.inner);
+        b.field("idle_threshold", &self.idle_threshold);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_auto_push_writer() {
+    use crate::CountingWriter;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let start = Instant::now();
+    let time = Rc::new(Cell::new(start));
+    let clock = {
+        let time = Rc::clone(&time);
+        move || time.get()
+    };
+    let advance = |millis| time.set(time.get() + Duration::from_millis(millis));
+
+    let inner = CountingWriter::new(io::Cursor::new(Vec::new()));
+    let mut writer = AutoPushWriter::with_clock(inner, Duration::from_millis(100), clock);
+
+    // Writes within the threshold aren't pushed.
+    writer.write_all(b"hello").unwrap();
+    advance(50);
+    writer.write_all(b" ").unwrap();
+    advance(50);
+    writer.flush_with_status(Status::active()).unwrap();
+    assert_eq!(writer.get_ref().flush_count(), 0);
+
+    // Once the data has been idle for the threshold, the next write pushes
+    // it first.
+    advance(50);
+    writer.write_all(b"world").unwrap();
+    assert_eq!(writer.get_ref().flush_count(), 1);
+    assert_eq!(writer.get_ref().bytes_written(), 11);
+
+    // An active flush also checks the timer.
+    advance(100);
+    writer.flush_with_status(Status::active()).unwrap();
+    assert_eq!(writer.get_ref().flush_count(), 2);
+
+    // Nothing has been written since the push, so there's nothing to push.
+    advance(100);
+    writer.flush_with_status(Status::active()).unwrap();
+    writer.write_all(b"!").unwrap();
+    assert_eq!(writer.get_ref().flush_count(), 2);
+
+    writer.set_idle_threshold(Duration::from_millis(10));
+    advance(10);
+    writer.write_all(b"!").unwrap();
+    assert_eq!(writer.get_ref().flush_count(), 3);
+}
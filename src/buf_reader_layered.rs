@@ -0,0 +1,283 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored,
+    default_suggested_buffer_size, Bufferable, ReadLayered, Status,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Adds buffering to a [`ReadLayered`].
+///
+/// This is like [`std::io::BufReader`], but it preserves statuses. Each fill
+/// of the buffer is a single `read_with_status` on the inner stream, so when
+/// the inner stream pushes, the buffered data is returned right away, with
+/// the push reported along with its last byte, rather than waiting for more
+/// data to fill the buffer.
+pub struct BufReaderLayered<Inner> {
+    inner: Inner,
+    buf: Vec<u8>,
+    pos: usize,
+    filled: usize,
+    status: Status,
+}
+
+impl<Inner: ReadLayered> BufReaderLayered<Inner> {
+    /// Construct a new `BufReaderLayered` which wraps `inner`, with a buffer
+    /// of the size `inner` suggests.
+    pub fn new(inner: Inner) -> Self {
+        let capacity = match inner.suggested_buffer_size() {
+            0 => default_suggested_buffer_size(&inner),
+            size => size,
+        };
+        Self::with_capacity(capacity, inner)
+    }
+
+    /// Construct a new `BufReaderLayered` which wraps `inner`, with a buffer
+    /// of at least `capacity` bytes.
+    ///
+    /// The buffer is made larger if `inner` requires it.
+    pub fn with_capacity(capacity: usize, inner: Inner) -> Self {
+        let capacity = capacity.max(inner.minimum_buffer_size()).max(1);
+        Self {
+            inner,
+            buf: vec![0; capacity],
+            pos: 0,
+            filled: 0,
+            status: Status::active(),
+        }
+    }
+
+    /// Return the data in the buffer, filling it with a read from the inner
+    /// stream first if it's empty, along with the status of the end of the
+    /// returned data.
+    ///
+    /// Use [`BufReaderLayered::consume`] to mark data as read.
+    pub fn fill_buf_with_status(&mut self) -> io::Result<(&[u8], Status)> {
+        if self.pos == self.filled {
+            self.pos = 0;
+            self.filled = 0;
+            let (size, status) = self.inner.read_with_status(&mut self.buf)?;
+            self.filled = size;
+            self.status = status;
+        }
+        Ok((&self.buf[self.pos..self.filled], self.status))
+    }
+
+    /// Mark `amt` bytes of the data returned by
+    /// [`BufReaderLayered::fill_buf_with_status`] as read.
+    pub fn consume(&mut self, amt: usize) {
+        self.pos = (self.pos + amt).min(self.filled);
+        // Once a push has been consumed, lookahead may continue past it.
+        if self.pos == self.filled && !self.status.is_end() {
+            self.status = Status::active();
+        }
+    }
+
+    /// Return up to `n` bytes of upcoming data without consuming them.
+    ///
+    /// This reads from the inner stream as needed, and grows the buffer if
+    /// `n` is larger than it. Fewer than `n` bytes are returned if the inner
+    /// stream pushes or ends first; lookahead doesn't extend past a push.
+    pub fn peek_n(&mut self, n: usize) -> io::Result<&[u8]> {
+        while self.filled - self.pos < n && self.status == Status::active() {
+            if self.buf.len() - self.pos < n {
+                self.buf.copy_within(self.pos..self.filled, 0);
+                self.filled -= self.pos;
+                self.pos = 0;
+                if self.buf.len() < n {
+                    self.buf.resize(n, 0);
+                }
+            }
+            let (size, status) = self.inner.read_with_status(&mut self.buf[self.filled..])?;
+            self.filled += size;
+            self.status = status;
+        }
+        Ok(&self.buf[self.pos..self.filled.min(self.pos + n)])
+    }
+
+    /// Return the data in the buffer, without reading from the inner
+    /// stream.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf[self.pos..self.filled]
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// It is inadvisable to directly read from the underlying reader.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `BufReaderLayered` and return the inner stream.
+    ///
+    /// Any data in the buffer is lost.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+}
+
+impl<Inner: ReadLayered> ReadLayered for BufReaderLayered<Inner> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        // If the buffer is empty and the read is large, skip the buffer.
+        if self.pos == self.filled && buf.len() >= self.buf.len() {
+            return self.inner.read_with_status(buf);
+        }
+
+        let (data, status) = self.fill_buf_with_status()?;
+        let size = data.len().min(buf.len());
+        buf[..size].copy_from_slice(&data[..size]);
+        self.consume(size);
+        if self.pos == self.filled {
+            Ok((size, status))
+        } else {
+            Ok((size, Status::active()))
+        }
+    }
+
+    /// Peeks using [`BufReaderLayered::peek_n`], which grows the buffer as
+    /// needed, and doesn't look past a push.
+    fn peek(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        let data = self.peek_n(buf.len())?;
+        let size = data.len();
+        buf[..size].copy_from_slice(data);
+        if self.pos + size == self.filled {
+            Ok((size, self.status))
+        } else {
+            Ok((size, Status::active()))
+        }
+    }
+}
+
+impl<Inner: ReadLayered> Bufferable for BufReaderLayered<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.buf = Vec::new();
+        self.pos = 0;
+        self.filled = 0;
+        self.status = Status::End;
+        self.inner.abandon()
+    }
+}
+
+impl<Inner: ReadLayered> Read for BufReaderLayered<Inner> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for BufReaderLayered<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("BufReaderLayered");
+        b.field("inner", &self.inner);
+        b.field(
+            "buffer",
+            &format_args!("{}/{}", self.filled - self.pos, self.buf.len()),
+        );
+        b.field("status", &self.status);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_buf_reader_layered_push() {
+    use crate::PacketReader;
+
+    let packets = vec![b"ab".to_vec(), b"cdef".to_vec()];
+    let mut reader = BufReaderLayered::with_capacity(8, PacketReader::new(packets));
+    let mut buf = [0; 1];
+    let mut reads = Vec::new();
+    loop {
+        let (size, status) = reader.read_with_status(&mut buf).unwrap();
+        reads.push((buf[..size].to_vec(), status));
+        if status.is_end() {
+            break;
+        }
+    }
+    assert_eq!(
+        reads,
+        [
+            (b"a".to_vec(), Status::active()),
+            (b"b".to_vec(), Status::push()),
+            (b"c".to_vec(), Status::active()),
+            (b"d".to_vec(), Status::active()),
+            (b"e".to_vec(), Status::active()),
+            (b"f".to_vec(), Status::End),
+        ]
+    );
+}
+
+#[test]
+fn test_buf_reader_layered_fill_buf() {
+    use crate::PacketReader;
+
+    let packets = vec![b"hello".to_vec(), b"world".to_vec()];
+    let mut reader = BufReaderLayered::new(PacketReader::new(packets));
+    let (data, status) = reader.fill_buf_with_status().unwrap();
+    assert_eq!((data, status), (&b"hello"[..], Status::push()));
+    reader.consume(2);
+    assert_eq!(reader.buffer(), b"llo");
+    reader.consume(3);
+    let (data, status) = reader.fill_buf_with_status().unwrap();
+    assert_eq!((data, status), (&b"world"[..], Status::End));
+}
+
+#[test]
+fn test_buf_reader_layered_peek_n() {
+    use crate::{LayeredReader, PacketReader};
+
+    let inner = LayeredReader::new(io::Cursor::new(b"hello world"));
+    let mut reader = BufReaderLayered::with_capacity(4, inner);
+    assert_eq!(reader.peek_n(7).unwrap(), b"hello w");
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "hello world");
+
+    // Lookahead stops at a push.
+    let packets = vec![b"ab".to_vec(), b"cd".to_vec()];
+    let mut reader = BufReaderLayered::new(PacketReader::new(packets));
+    assert_eq!(reader.peek_n(3).unwrap(), b"ab");
+    let mut buf = [0; 8];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (2, Status::push())
+    );
+    assert_eq!(reader.peek_n(3).unwrap(), b"cd");
+}
+
+#[test]
+fn test_buf_reader_layered_read_layered_peek() {
+    use crate::PacketReader;
+
+    let packets = vec![b"GIF8".to_vec(), b"9a".to_vec()];
+    let mut reader = BufReaderLayered::new(PacketReader::new(packets));
+    let mut magic = [0; 3];
+    assert_eq!(reader.peek(&mut magic).unwrap(), (3, Status::active()));
+    assert_eq!(&magic, b"GIF");
+    let mut buf = [0; 8];
+    assert_eq!(reader.peek(&mut buf).unwrap(), (4, Status::push()));
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (4, Status::push())
+    );
+    assert_eq!(&buf[..4], b"GIF8");
+}
@@ -0,0 +1,172 @@
+use crate::{default_suggested_buffer_size, Bufferable, WriteLayered};
+use std::fmt;
+use std::io::{self, Write};
+
+/// Adds buffering to a [`WriteLayered`].
+///
+/// This is like [`std::io::BufWriter`], but it's aware of statuses. Data is
+/// held in the buffer until it fills, or until a push, a plain
+/// [`Write::flush`], or [`WriteLayered::close`]. So a
+/// `flush_with_status(Status::active())` leaves data buffered, while a
+/// `flush_with_status(Status::push())` sends it on its way.
+pub struct BufWriterLayered<Inner> {
+    inner: Inner,
+    buf: Vec<u8>,
+    capacity: usize,
+}
+
+impl<Inner: WriteLayered> BufWriterLayered<Inner> {
+    /// Construct a new `BufWriterLayered` which wraps `inner`, with a buffer
+    /// of the size `inner` suggests.
+    pub fn new(inner: Inner) -> Self {
+        let capacity = match inner.suggested_buffer_size() {
+            0 => default_suggested_buffer_size(&inner),
+            size => size,
+        };
+        Self::with_capacity(capacity, inner)
+    }
+
+    /// Construct a new `BufWriterLayered` which wraps `inner`, with a buffer
+    /// of `capacity` bytes.
+    pub fn with_capacity(capacity: usize, inner: Inner) -> Self {
+        Self {
+            inner,
+            buf: Vec::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Return the data in the buffer.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `BufWriterLayered` and return the inner stream.
+    ///
+    /// Any data in the buffer is lost.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+
+    /// Write the buffered data to the inner stream.
+    fn flush_buf(&mut self) -> io::Result<()> {
+        let mut written = 0;
+        let result = loop {
+            if written == self.buf.len() {
+                break Ok(());
+            }
+            match self.inner.write(&self.buf[written..]) {
+                Ok(0) => {
+                    break Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "failed to write the buffered data",
+                    ))
+                }
+                Ok(size) => written += size,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => break Err(e),
+            }
+        };
+        self.buf.drain(..written);
+        result
+    }
+}
+
+impl<Inner: WriteLayered> WriteLayered for BufWriterLayered<Inner> {
+    fn close(&mut self) -> io::Result<()> {
+        self.flush_buf()?;
+        self.inner.close()
+    }
+}
+
+impl<Inner: WriteLayered> Bufferable for BufWriterLayered<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.buf = Vec::new();
+        self.inner.abandon()
+    }
+}
+
+impl<Inner: WriteLayered> Write for BufWriterLayered<Inner> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.buf.len() + buf.len() > self.capacity {
+            self.flush_buf()?;
+        }
+        if buf.len() >= self.capacity {
+            self.inner.write(buf)
+        } else {
+            self.buf.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+    }
+
+    /// Write the buffered data to the inner stream, and flush it.
+    ///
+    /// A `flush_with_status(Status::push())` comes here by way of the
+    /// default [`WriteLayered::flush_with_status`].
+    fn flush(&mut self) -> io::Result<()> {
+        self.flush_buf()?;
+        self.inner.flush()
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for BufWriterLayered<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("BufWriterLayered");
+        b.field("inner", &self.inner);
+        b.field(
+            "buffer",
+            &format_args!("{}/{}", self.buf.len(), self.capacity),
+        );
+        b.finish()
+    }
+}
+
+#[test]
+fn test_buf_writer_layered() {
+    use crate::{CloseTrackingWriter, Status};
+
+    let inner = CloseTrackingWriter::new(io::Cursor::new(Vec::new()));
+    let mut writer = BufWriterLayered::with_capacity(16, inner);
+    writer.write_all(b"hello ").unwrap();
+    writer.flush_with_status(Status::active()).unwrap();
+    assert_eq!(writer.get_ref().get_ref().get_ref(), b"");
+    assert_eq!(writer.buffer(), b"hello ");
+
+    writer.flush_with_status(Status::push()).unwrap();
+    assert_eq!(writer.get_ref().get_ref().get_ref(), b"hello ");
+    assert_eq!(writer.buffer(), b"");
+
+    writer.write_all(b"world").unwrap();
+    writer.flush().unwrap();
+    assert_eq!(writer.get_ref().get_ref().get_ref(), b"hello world");
+
+    writer.write_all(b"!").unwrap();
+    writer.close().unwrap();
+    assert!(writer.get_ref().was_closed());
+    assert_eq!(writer.get_ref().get_ref().get_ref(), b"hello world!");
+}
+
+#[test]
+fn test_buf_writer_layered_full() {
+    let mut writer = BufWriterLayered::with_capacity(4, io::Cursor::new(Vec::new()));
+    writer.write_all(b"abc").unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"");
+    writer.write_all(b"de").unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"abc");
+    writer.write_all(b"fghij").unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"abcdefghij");
+    assert_eq!(writer.buffer(), b"");
+}
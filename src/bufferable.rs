@@ -1,5 +1,7 @@
-use std::ops::DerefMut;
-use std::pin::Pin;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::ops::DerefMut;
+use core::pin::Pin;
 
 /// A trait to help with buffering on top of `ReadLayered` and `WriteLayered`.
 pub trait Bufferable {
@@ -45,6 +47,7 @@ impl<B: Bufferable> Bufferable for &mut B {
     }
 }
 
+#[cfg(feature = "std")]
 impl Bufferable for std::io::Cursor<Vec<u8>> {
     #[inline]
     fn abandon(&mut self) {
@@ -57,6 +60,7 @@ impl Bufferable for std::io::Cursor<Vec<u8>> {
     }
 }
 
+#[cfg(feature = "std")]
 impl Bufferable for std::io::Cursor<Box<[u8]>> {
     #[inline]
     fn abandon(&mut self) {
@@ -69,6 +73,7 @@ impl Bufferable for std::io::Cursor<Box<[u8]>> {
     }
 }
 
+#[cfg(feature = "std")]
 impl Bufferable for std::io::Cursor<&mut Vec<u8>> {
     #[inline]
     fn abandon(&mut self) {
@@ -81,6 +86,7 @@ impl Bufferable for std::io::Cursor<&mut Vec<u8>> {
     }
 }
 
+#[cfg(feature = "std")]
 impl Bufferable for std::io::Cursor<&mut [u8]> {
     #[inline]
     fn abandon(&mut self) {
@@ -93,6 +99,46 @@ impl Bufferable for std::io::Cursor<&mut [u8]> {
     }
 }
 
+#[cfg(feature = "std")]
+impl Bufferable for std::io::Empty {
+    #[inline]
+    fn abandon(&mut self) {}
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        0
+    }
+}
+
+#[cfg(feature = "std")]
+impl Bufferable for std::io::Repeat {
+    #[inline]
+    fn abandon(&mut self) {}
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        // This is just writing values to memory, so no need to buffer.
+        0
+    }
+}
+
+#[cfg(feature = "std")]
+impl Bufferable for std::io::Sink {
+    #[inline]
+    fn abandon(&mut self) {}
+}
+
+impl Bufferable for Vec<u8> {
+    #[inline]
+    fn abandon(&mut self) {}
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        // Appending never blocks, so there's no need to buffer.
+        0
+    }
+}
+
 impl<P> Bufferable for Pin<P>
 where
     P: DerefMut + Unpin,
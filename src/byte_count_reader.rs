@@ -0,0 +1,101 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    ReadLayered, Status,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Adapts a [`ReadLayered`] to count the occurrences of a byte in the data
+/// read from it.
+///
+/// Data and statuses are passed through unchanged. Counting `b'\n'` gives a
+/// line count, as with `wc -l`.
+pub struct ByteCountReader<Inner> {
+    inner: Inner,
+    target: u8,
+    count: u64,
+}
+
+impl<Inner: ReadLayered> ByteCountReader<Inner> {
+    /// Construct a new `ByteCountReader` which wraps `inner` and counts
+    /// occurrences of `target`.
+    pub fn new(inner: Inner, target: u8) -> Self {
+        Self {
+            inner,
+            target,
+            count: 0,
+        }
+    }
+
+    /// Return the number of occurrences of the target byte read so far.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Consume this `ByteCountReader` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+}
+
+impl<Inner: ReadLayered> ReadLayered for ByteCountReader<Inner> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        let (size, status) = self.inner.read_with_status(buf)?;
+        let target = self.target;
+        self.count += buf[..size].iter().filter(|b| **b == target).count() as u64;
+        Ok((size, status))
+    }
+
+    #[inline]
+    fn minimum_buffer_size(&self) -> usize {
+        self.inner.minimum_buffer_size()
+    }
+}
+
+impl<Inner: ReadLayered> Bufferable for ByteCountReader<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon()
+    }
+}
+
+impl<Inner: ReadLayered> Read for ByteCountReader<Inner> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for ByteCountReader<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("ByteCountReader");
+        b.field("inner", &self.inner);
+        b.field("target", &self.target);
+        b.field("count", &self.count);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_byte_count_reader() {
+    let mut reader = ByteCountReader::new(crate::SliceReader::new(b"one\ntwo\n\nfour"), b'\n');
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "one\ntwo\n\nfour");
+    assert_eq!(reader.count(), 3);
+}
@@ -0,0 +1,152 @@
+use crate::{default_suggested_buffer_size, ReadLayered, Status};
+use std::fmt;
+use std::io;
+
+/// An iterator over the bytes of a [`ReadLayered`], with the status of each
+/// byte.
+///
+/// This is returned by [`ReadLayered::bytes_with_status`]. Unlike
+/// [`Read::bytes`], it shows where pushes fall between bytes: a byte which
+/// completes a read reporting a push carries `Status::push()`, and the last
+/// byte of the stream carries `Status::End`, after which the iterator stops.
+/// Other bytes carry `Status::active()`.
+///
+/// Input is read into an internal buffer. When the last buffered byte was
+/// read with `Status::active()`, more is read before the byte is returned,
+/// so that the last byte of the stream carries `Status::End` even when the
+/// inner stream reports the end with a separate empty read.
+///
+/// [`Read::bytes`]: std::io::Read::bytes
+pub struct BytesWithStatus<Inner> {
+    inner: Inner,
+    buf: Vec<u8>,
+    pos: usize,
+    status: Status,
+    done: bool,
+}
+
+impl<Inner: ReadLayered> BytesWithStatus<Inner> {
+    pub(crate) fn new(inner: Inner) -> Self {
+        Self {
+            inner,
+            buf: Vec::new(),
+            pos: 0,
+            status: Status::active(),
+            done: false,
+        }
+    }
+
+    /// Consume this `BytesWithStatus` and return the inner stream.
+    ///
+    /// Any buffered input which hasn't been returned yet is discarded.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+
+    /// Read more input into the buffer, after the unreturned bytes.
+    fn fill(&mut self) -> io::Result<()> {
+        self.buf.drain(..self.pos);
+        self.pos = 0;
+        let chunk =
+            default_suggested_buffer_size(&self.inner).max(self.inner.minimum_buffer_size());
+        let start = self.buf.len();
+        self.buf.resize(start + chunk, 0);
+        let result = self.inner.read_with_status(&mut self.buf[start..]);
+        let (size, status) = match result {
+            Ok(size_and_status) => size_and_status,
+            Err(e) => {
+                self.buf.truncate(start);
+                return Err(e);
+            }
+        };
+        self.buf.truncate(start + size);
+        self.status = status;
+        Ok(())
+    }
+}
+
+impl<Inner: ReadLayered> Iterator for BytesWithStatus<Inner> {
+    type Item = io::Result<(u8, Status)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.done {
+                return None;
+            }
+            let remaining = self.buf.len() - self.pos;
+            if remaining > 1 || (remaining == 1 && !self.status.is_active()) {
+                let byte = self.buf[self.pos];
+                self.pos += 1;
+                let status = if self.pos == self.buf.len() {
+                    self.status
+                } else {
+                    Status::active()
+                };
+                self.done = status.is_end();
+                return Some(Ok((byte, status)));
+            }
+            if remaining == 0 && self.status.is_end() {
+                self.done = true;
+                return None;
+            }
+            if remaining == 0 && self.status.is_push() {
+                // The push was reported with the previous byte.
+                self.status = Status::active();
+            }
+            if let Err(e) = self.fill() {
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for BytesWithStatus<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("BytesWithStatus");
+        b.field("inner", &self.inner);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_bytes_with_status() {
+    use crate::SliceReader;
+
+    let bytes: Vec<_> = SliceReader::new(b"abc")
+        .bytes_with_status()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(
+        bytes,
+        [
+            (b'a', Status::active()),
+            (b'b', Status::active()),
+            (b'c', Status::End),
+        ]
+    );
+}
+
+#[test]
+fn test_bytes_with_status_push() {
+    use crate::{LayeredReader, PacketReader};
+
+    let packets = vec![b"ab".to_vec(), b"c".to_vec()];
+    let bytes: Vec<_> = PacketReader::new(packets)
+        .bytes_with_status()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(
+        bytes,
+        [
+            (b'a', Status::active()),
+            (b'b', Status::push()),
+            (b'c', Status::End),
+        ]
+    );
+
+    // The end is read ahead when it's reported with a separate empty read.
+    let mut bytes = LayeredReader::new(io::Cursor::new(b"xy")).bytes_with_status();
+    assert_eq!(bytes.next().unwrap().unwrap(), (b'x', Status::active()));
+    assert_eq!(bytes.next().unwrap().unwrap(), (b'y', Status::End));
+    assert!(bytes.next().is_none());
+}
@@ -0,0 +1,138 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    ChainWithReader, ReadLayered, Status,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Reads from one [`ReadLayered`] until it ends, then from a second.
+///
+/// Unlike [`Read::chain`], this doesn't treat a 0-length read as the end of
+/// the first stream, and it preserves statuses: `Status::End` is only
+/// reported when the second stream ends, and pushes from either stream are
+/// forwarded. This is returned by [`chain_layered`], and is a
+/// [`ChainWithReader`] with an empty separator.
+pub struct ChainLayered<T, U> {
+    inner: ChainWithReader<T, U>,
+}
+
+impl<T: ReadLayered, U: ReadLayered> ChainLayered<T, U> {
+    /// Construct a new `ChainLayered` which reads `first`, then `second`.
+    pub fn new(first: T, second: U) -> Self {
+        Self {
+            inner: ChainWithReader::new(first, Vec::new(), second),
+        }
+    }
+
+    /// Gets references to the inner streams.
+    pub fn get_ref(&self) -> (&T, &U) {
+        self.inner.get_ref()
+    }
+
+    /// Gets mutable references to the inner streams.
+    ///
+    /// It is inadvisable to directly read from the inner streams.
+    pub fn get_mut(&mut self) -> (&mut T, &mut U) {
+        self.inner.get_mut()
+    }
+
+    /// Consume this `ChainLayered` and return the inner streams.
+    pub fn into_inner(self) -> (T, U) {
+        self.inner.into_inner()
+    }
+}
+
+/// Return a reader which reads `first` until it ends, then `second`.
+#[inline]
+pub fn chain_layered<T: ReadLayered, U: ReadLayered>(first: T, second: U) -> ChainLayered<T, U> {
+    ChainLayered::new(first, second)
+}
+
+impl<T: ReadLayered, U: ReadLayered> ReadLayered for ChainLayered<T, U> {
+    #[inline]
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        self.inner.read_with_status(buf)
+    }
+
+    #[inline]
+    fn minimum_buffer_size(&self) -> usize {
+        self.inner.minimum_buffer_size()
+    }
+}
+
+impl<T: ReadLayered, U: ReadLayered> Bufferable for ChainLayered<T, U> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon()
+    }
+}
+
+impl<T: ReadLayered, U: ReadLayered> Read for ChainLayered<T, U> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<T: fmt::Debug, U: fmt::Debug> fmt::Debug for ChainLayered<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("ChainLayered");
+        b.field("inner", &self.inner);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_chain_layered() {
+    use crate::SliceReader;
+
+    let mut reader = chain_layered(SliceReader::new(b"hello "), SliceReader::new(b"world"));
+    let mut buf = [0; 8];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (6, Status::active())
+    );
+    assert_eq!(&buf[..6], b"hello ");
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (5, Status::End));
+    assert_eq!(&buf[..5], b"world");
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (0, Status::End));
+
+    let mut reader = chain_layered(SliceReader::new(b""), SliceReader::new(b"world"));
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "world");
+}
+
+#[test]
+fn test_chain_layered_push() {
+    use crate::{PacketReader, SliceReader};
+
+    let packets = vec![b"ab".to_vec(), b"cd".to_vec()];
+    let mut reader = chain_layered(PacketReader::new(packets), SliceReader::new(b"ef"));
+    let mut buf = [0; 8];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (2, Status::push())
+    );
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (2, Status::active())
+    );
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (2, Status::End));
+    assert_eq!(&buf[..2], b"ef");
+}
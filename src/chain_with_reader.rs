@@ -0,0 +1,164 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    ReadLayered, Status,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Reads from one [`ReadLayered`] until it ends, then a separator, then a
+/// second [`ReadLayered`].
+///
+/// This is returned by [`ReadLayered::chain_with`]. `Status::End` is only
+/// reported when the second stream ends; pushes from either stream are
+/// forwarded.
+pub struct ChainWithReader<A, B> {
+    first: A,
+    sep: Vec<u8>,
+    sep_pos: usize,
+    second: B,
+    state: State,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    First,
+    Sep,
+    Second,
+}
+
+impl<A: ReadLayered, B: ReadLayered> ChainWithReader<A, B> {
+    /// Construct a new `ChainWithReader` which reads `first`, then `sep`,
+    /// then `second`.
+    pub fn new(first: A, sep: Vec<u8>, second: B) -> Self {
+        Self {
+            first,
+            sep,
+            sep_pos: 0,
+            second,
+            state: State::First,
+        }
+    }
+
+    /// Gets references to the inner streams.
+    pub fn get_ref(&self) -> (&A, &B) {
+        (&self.first, &self.second)
+    }
+
+    /// Gets mutable references to the inner streams.
+    ///
+    /// It is inadvisable to directly read from the inner streams.
+    pub fn get_mut(&mut self) -> (&mut A, &mut B) {
+        (&mut self.first, &mut self.second)
+    }
+
+    /// Consume this `ChainWithReader` and return the inner streams.
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A: ReadLayered, B: ReadLayered> ReadLayered for ChainWithReader<A, B> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        if self.state == State::First {
+            let (size, status) = self.first.read_with_status(buf)?;
+            if !status.is_end() {
+                return Ok((size, status));
+            }
+            self.state = State::Sep;
+            if size != 0 {
+                return Ok((size, Status::active()));
+            }
+        }
+
+        if self.state == State::Sep {
+            let size = (self.sep.len() - self.sep_pos).min(buf.len());
+            buf[..size].copy_from_slice(&self.sep[self.sep_pos..self.sep_pos + size]);
+            self.sep_pos += size;
+            if self.sep_pos == self.sep.len() {
+                self.state = State::Second;
+            }
+            if size != 0 {
+                return Ok((size, Status::active()));
+            }
+        }
+
+        self.second.read_with_status(buf)
+    }
+
+    #[inline]
+    fn minimum_buffer_size(&self) -> usize {
+        self.first
+            .minimum_buffer_size()
+            .max(self.second.minimum_buffer_size())
+    }
+}
+
+impl<A: ReadLayered, B: ReadLayered> Bufferable for ChainWithReader<A, B> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.first.abandon();
+        self.second.abandon();
+        self.sep_pos = self.sep.len();
+        self.state = State::Second;
+    }
+}
+
+impl<A: ReadLayered, B: ReadLayered> Read for ChainWithReader<A, B> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<A: fmt::Debug, B: fmt::Debug> fmt::Debug for ChainWithReader<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("ChainWithReader");
+        b.field("first", &self.first);
+        b.field("sep", &self.sep);
+        b.field("second", &self.second);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_chain_with() {
+    use crate::SliceReader;
+
+    let mut reader =
+        SliceReader::new(b"alpha").chain_with(b", ".to_vec(), SliceReader::new(b"beta"));
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "alpha, beta");
+}
+
+#[test]
+fn test_chain_with_statuses() {
+    use crate::SliceReader;
+
+    let mut reader = SliceReader::new(b"ab").chain_with(b", ".to_vec(), SliceReader::new(b"c"));
+    let mut buf = [0; 8];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (2, Status::active())
+    );
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (2, Status::active())
+    );
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (1, Status::End));
+}
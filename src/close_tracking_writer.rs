@@ -0,0 +1,111 @@
+use crate::{Bufferable, WriteLayered};
+use std::fmt;
+use std::io::{self, Write};
+
+/// Adapts a [`WriteLayered`] to record whether [`WriteLayered::close`] was
+/// called.
+///
+/// [`LayeredWriter`] panics on drop if it wasn't closed or abandoned. In
+/// tests, it's often more convenient to check for that explicitly, with
+/// [`CloseTrackingWriter::was_closed`]. A `flush_with_status(Status::End)`
+/// counts as a close, by way of the default
+/// [`WriteLayered::flush_with_status`].
+///
+/// [`LayeredWriter`]: crate::LayeredWriter
+pub struct CloseTrackingWriter<Inner> {
+    inner: Inner,
+    closed: bool,
+}
+
+impl<Inner: WriteLayered> CloseTrackingWriter<Inner> {
+    /// Construct a new `CloseTrackingWriter` which wraps `inner`.
+    pub fn new(inner: Inner) -> Self {
+        Self {
+            inner,
+            closed: false,
+        }
+    }
+
+    /// Return `true` if `close` has been called, even if it failed.
+    pub fn was_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `CloseTrackingWriter` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+}
+
+impl<Inner: WriteLayered> WriteLayered for CloseTrackingWriter<Inner> {
+    #[inline]
+    fn close(&mut self) -> io::Result<()> {
+        self.closed = true;
+        self.inner.close()
+    }
+}
+
+impl<Inner: WriteLayered> Bufferable for CloseTrackingWriter<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: WriteLayered> Write for CloseTrackingWriter<Inner> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for CloseTrackingWriter<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("CloseTrackingWriter");
+        b.field("inner", &self.inner);
+        b.field("closed", &self.closed);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_close_tracking_writer() {
+    use crate::Status;
+
+    let mut writer = CloseTrackingWriter::new(io::Cursor::new(Vec::new()));
+    writer.write_all(b"data").unwrap();
+    writer.flush_with_status(Status::push()).unwrap();
+    assert!(!writer.was_closed());
+    writer.close().unwrap();
+    assert!(writer.was_closed());
+
+    let mut writer = CloseTrackingWriter::new(io::Cursor::new(Vec::new()));
+    writer.flush_with_status(Status::End).unwrap();
+    assert!(writer.was_closed());
+
+    let mut writer = CloseTrackingWriter::new(io::Cursor::new(Vec::new()));
+    writer.abandon();
+    assert!(!writer.was_closed());
+}
@@ -0,0 +1,39 @@
+use std::io;
+
+/// A decoder for splitting a byte stream into frames, for use with
+/// [`FramedReader`].
+///
+/// [`FramedReader`]: crate::FramedReader
+pub trait Decoder {
+    /// Try to decode a frame from the front of `src`.
+    ///
+    /// If `src` holds a complete frame, remove its bytes from `src` and
+    /// return its contents. Otherwise, return `None` to request more input.
+    fn decode(&mut self, src: &mut Vec<u8>) -> io::Result<Option<Vec<u8>>>;
+
+    /// Like `decode`, but called once the input stream has ended, so no more
+    /// input will arrive.
+    ///
+    /// The default implementation calls `decode`, and fails with
+    /// `io::ErrorKind::InvalidData` if that leaves an incomplete frame in
+    /// `src`.
+    fn decode_eof(&mut self, src: &mut Vec<u8>) -> io::Result<Option<Vec<u8>>> {
+        match self.decode(src)? {
+            Some(frame) => Ok(Some(frame)),
+            None if src.is_empty() => Ok(None),
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "incomplete frame at end of stream",
+            )),
+        }
+    }
+}
+
+/// An encoder for writing frames to a byte stream, for use with
+/// [`FramedWriter`].
+///
+/// [`FramedWriter`]: crate::FramedWriter
+pub trait Encoder {
+    /// Encode `frame`, appending the encoded bytes to `dst`.
+    fn encode(&mut self, frame: &[u8], dst: &mut Vec<u8>) -> io::Result<()>;
+}
@@ -0,0 +1,221 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored,
+    default_suggested_buffer_size, Bufferable, Decompressor, ReadLayered, Status,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Adapts a [`ReadLayered`] to decompress the data read from it with a
+/// [`Decompressor`].
+///
+/// `Status::End` is reported once the inner stream has ended, all of its data
+/// has been decompressed and returned, and the decompressor reports that the
+/// compressed stream is finished. If the inner stream ends before then, the
+/// read fails with `io::ErrorKind::UnexpectedEof`.
+pub struct CodecReader<Inner, D> {
+    inner: Inner,
+    decompressor: D,
+    src: Vec<u8>,
+    out: Vec<u8>,
+    out_pos: usize,
+    inner_ended: bool,
+}
+
+impl<Inner: ReadLayered, D: Decompressor> CodecReader<Inner, D> {
+    /// Construct a new `CodecReader` which wraps `inner` and decompresses
+    /// with `decompressor`.
+    pub fn new(inner: Inner, decompressor: D) -> Self {
+        Self {
+            inner,
+            decompressor,
+            src: Vec::new(),
+            out: Vec::new(),
+            out_pos: 0,
+            inner_ended: false,
+        }
+    }
+
+    /// Gets a reference to the decompressor.
+    pub fn decompressor(&self) -> &D {
+        &self.decompressor
+    }
+
+    /// Consume this `CodecReader` and return the inner stream.
+    ///
+    /// Any buffered input which hasn't been decompressed yet is discarded.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+
+    /// Decompress as much buffered input as possible, and return the number
+    /// of bytes consumed.
+    fn decompress(&mut self) -> io::Result<usize> {
+        let consumed = self.decompressor.decompress(&self.src, &mut self.out)?;
+        self.src.drain(..consumed);
+        Ok(consumed)
+    }
+
+    /// Produce more decompressed output, reading from the inner stream as
+    /// needed. Return `Some` status if no output is available yet.
+    fn fill_out(&mut self) -> io::Result<Option<Status>> {
+        loop {
+            if !self.src.is_empty() {
+                let consumed = self.decompress()?;
+                if !self.out.is_empty() {
+                    return Ok(None);
+                }
+                if consumed == 0 && self.inner_ended {
+                    return Err(unexpected_eof());
+                }
+            }
+            if self.inner_ended {
+                return if self.decompressor.is_finished() {
+                    Ok(Some(Status::End))
+                } else {
+                    Err(unexpected_eof())
+                };
+            }
+
+            let chunk =
+                default_suggested_buffer_size(&self.inner).max(self.inner.minimum_buffer_size());
+            let start = self.src.len();
+            self.src.resize(start + chunk, 0);
+            let result = self.inner.read_with_status(&mut self.src[start..]);
+            let (size, status) = match result {
+                Ok(size_and_status) => size_and_status,
+                Err(e) => {
+                    self.src.truncate(start);
+                    return Err(e);
+                }
+            };
+            self.src.truncate(start + size);
+            if status.is_end() {
+                self.inner_ended = true;
+            } else if size == 0 {
+                return Ok(Some(Status::active()));
+            }
+        }
+    }
+}
+
+impl<Inner: ReadLayered, D: Decompressor> ReadLayered for CodecReader<Inner, D> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        if self.out_pos == self.out.len() {
+            self.out.clear();
+            self.out_pos = 0;
+            if let Some(status) = self.fill_out()? {
+                return Ok((0, status));
+            }
+        }
+
+        let size = (self.out.len() - self.out_pos).min(buf.len());
+        buf[..size].copy_from_slice(&self.out[self.out_pos..self.out_pos + size]);
+        self.out_pos += size;
+        Ok((size, Status::active()))
+    }
+}
+
+impl<Inner: ReadLayered, D> Bufferable for CodecReader<Inner, D> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon();
+        self.src.clear();
+        self.out.clear();
+        self.out_pos = 0;
+        self.inner_ended = true;
+    }
+}
+
+impl<Inner: ReadLayered, D: Decompressor> Read for CodecReader<Inner, D> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug, D> fmt::Debug for CodecReader<Inner, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("CodecReader");
+        b.field("inner", &self.inner);
+        b.finish()
+    }
+}
+
+fn unexpected_eof() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        "compressed stream ended unexpectedly",
+    )
+}
+
+#[test]
+fn test_codec_reader_round_trip() {
+    use crate::{CodecWriter, IdentityCodec, SliceReader, WriteLayered};
+    use std::io::Write;
+
+    let mut writer = CodecWriter::new(io::Cursor::new(Vec::new()), IdentityCodec::new());
+    writer.write_all(b"hello world").unwrap();
+    writer.close().unwrap();
+
+    let compressed = writer.get_ref().get_ref();
+    let mut reader = CodecReader::new(SliceReader::new(compressed), IdentityCodec::new());
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "hello world");
+}
+
+#[test]
+fn test_codec_reader_unfinished() {
+    use crate::SliceReader;
+
+    // A decompressor which expects a `;` terminator.
+    struct Terminated(bool);
+
+    impl Decompressor for Terminated {
+        fn decompress(&mut self, input: &[u8], output: &mut Vec<u8>) -> io::Result<usize> {
+            match input.iter().position(|b| *b == b';') {
+                Some(end) => {
+                    output.extend_from_slice(&input[..end]);
+                    self.0 = true;
+                    Ok(end + 1)
+                }
+                None => {
+                    output.extend_from_slice(input);
+                    Ok(input.len())
+                }
+            }
+        }
+
+        fn is_finished(&self) -> bool {
+            self.0
+        }
+    }
+
+    let mut reader = CodecReader::new(SliceReader::new(b"abc;"), Terminated(false));
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "abc");
+
+    let mut reader = CodecReader::new(SliceReader::new(b"abc"), Terminated(false));
+    let mut buf = [0; 8];
+    assert_eq!(reader.read_with_status(&mut buf).unwrap().0, 3);
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap_err().kind(),
+        io::ErrorKind::UnexpectedEof
+    );
+}
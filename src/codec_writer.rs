@@ -0,0 +1,177 @@
+use crate::{Bufferable, Compressor, WriteLayered};
+use std::fmt;
+use std::io::{self, Write};
+
+/// Adapts a [`WriteLayered`] to compress the data written to it with a
+/// [`Compressor`].
+///
+/// On [`WriteLayered::close`], the compressor is finished before the inner
+/// stream is closed. On [`Bufferable::abandon`], any data held by the
+/// compressor is discarded and nothing further is written.
+pub struct CodecWriter<Inner, C> {
+    inner: Inner,
+    compressor: C,
+    out: Vec<u8>,
+    ended: bool,
+}
+
+impl<Inner: WriteLayered, C: Compressor> CodecWriter<Inner, C> {
+    /// Construct a new `CodecWriter` which wraps `inner` and compresses with
+    /// `compressor`.
+    pub fn new(inner: Inner, compressor: C) -> Self {
+        Self {
+            inner,
+            compressor,
+            out: Vec::new(),
+            ended: false,
+        }
+    }
+
+    /// Gets a reference to the compressor.
+    pub fn compressor(&self) -> &C {
+        &self.compressor
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    fn check_open(&self) -> io::Result<()> {
+        if self.ended {
+            Err(stream_already_ended())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Write everything the compressor has produced to the inner stream.
+    fn write_out(&mut self) -> io::Result<()> {
+        let result = self.inner.write_all(&self.out);
+        self.out.clear();
+        result
+    }
+}
+
+impl<Inner: WriteLayered, C: Compressor> WriteLayered for CodecWriter<Inner, C> {
+    fn close(&mut self) -> io::Result<()> {
+        self.check_open()?;
+        self.ended = true;
+        self.compressor.finish(&mut self.out)?;
+        self.write_out()?;
+        self.inner.close()
+    }
+}
+
+impl<Inner: WriteLayered, C> Bufferable for CodecWriter<Inner, C> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.ended = true;
+        self.out = Vec::new();
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: WriteLayered, C: Compressor> Write for CodecWriter<Inner, C> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.check_open()?;
+        self.compressor.compress(buf, &mut self.out)?;
+        self.write_out()?;
+        Ok(buf.len())
+    }
+
+    /// Sync-flush the compressor, so that a decompressor can make progress
+    /// on everything written so far, and then flush the inner stream.
+    ///
+    /// A `flush_with_status(Status::push())` comes here by way of the
+    /// default [`WriteLayered::flush_with_status`].
+    fn flush(&mut self) -> io::Result<()> {
+        self.check_open()?;
+        self.compressor.sync_flush(&mut self.out)?;
+        self.write_out()?;
+        self.inner.flush()
+    }
+}
+
+impl<Inner: fmt::Debug, C> fmt::Debug for CodecWriter<Inner, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("CodecWriter");
+        b.field("inner", &self.inner);
+        b.finish()
+    }
+}
+
+fn stream_already_ended() -> io::Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, "stream has already ended")
+}
+
+#[test]
+fn test_codec_writer_identity() {
+    use crate::{IdentityCodec, ReadLayered, SliceReader, Status};
+
+    let mut writer = CodecWriter::new(io::Cursor::new(Vec::new()), IdentityCodec::new());
+    writer.write_all(b"hello ").unwrap();
+    writer.flush_with_status(Status::push()).unwrap();
+    writer.write_all(b"world").unwrap();
+    writer.close().unwrap();
+    assert!(writer.write_all(b"!").is_err());
+
+    let mut reader = SliceReader::new(writer.get_ref().get_ref());
+    let mut buf = [0; 16];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (11, Status::End)
+    );
+    assert_eq!(&buf[..11], b"hello world");
+}
+
+#[test]
+fn test_codec_writer_sync_flush() {
+    use crate::Status;
+
+    // A compressor which holds everything back until a flush, and brackets
+    // each flushed batch.
+    struct Batching(Vec<u8>);
+
+    impl Compressor for Batching {
+        fn compress(&mut self, input: &[u8], _output: &mut Vec<u8>) -> io::Result<()> {
+            self.0.extend_from_slice(input);
+            Ok(())
+        }
+
+        fn sync_flush(&mut self, output: &mut Vec<u8>) -> io::Result<()> {
+            output.push(b'[');
+            output.append(&mut self.0);
+            output.push(b']');
+            Ok(())
+        }
+
+        fn finish(&mut self, output: &mut Vec<u8>) -> io::Result<()> {
+            self.sync_flush(output)
+        }
+    }
+
+    let mut writer = CodecWriter::new(io::Cursor::new(Vec::new()), Batching(Vec::new()));
+    writer.write_all(b"ab").unwrap();
+    writer.write_all(b"c").unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"");
+    writer.flush_with_status(Status::push()).unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"[abc]");
+    writer.write_all(b"d").unwrap();
+    writer.flush_with_status(Status::active()).unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"[abc]");
+    writer.close().unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"[abc][d]");
+}
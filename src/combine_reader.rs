@@ -0,0 +1,163 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    ReadLayered, Status,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Reads from two [`ReadLayered`]s in lockstep, combining their bytes
+/// pairwise with a function.
+///
+/// For example, combining with `|a, b| a ^ b` XORs a stream with a keystream.
+/// `Status::End` is reported when either stream ends. Pushes from the first
+/// stream are forwarded.
+pub struct CombineReader<A, B, F> {
+    a: A,
+    b: B,
+    f: F,
+    /// Bytes read from `a` which haven't been combined yet.
+    held: Vec<u8>,
+    held_status: Status,
+    ended: bool,
+}
+
+impl<A: ReadLayered, B: ReadLayered, F: FnMut(u8, u8) -> u8> CombineReader<A, B, F> {
+    /// Construct a new `CombineReader` which combines bytes from `a` and `b`
+    /// with `f`.
+    pub fn new(a: A, b: B, f: F) -> Self {
+        Self {
+            a,
+            b,
+            f,
+            held: Vec::new(),
+            held_status: Status::active(),
+            ended: false,
+        }
+    }
+
+    /// Consume this `CombineReader` and return the inner streams.
+    pub fn into_inner(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+
+impl<A: ReadLayered, B: ReadLayered, F: FnMut(u8, u8) -> u8> ReadLayered
+    for CombineReader<A, B, F>
+{
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        if self.ended {
+            return Ok((0, Status::End));
+        }
+
+        if self.held.is_empty() {
+            let (size, status) = self.a.read_with_status(buf)?;
+            if size == 0 {
+                self.ended = status.is_end();
+                return Ok((0, status));
+            }
+            self.held.extend_from_slice(&buf[..size]);
+            self.held_status = status;
+        }
+
+        let len = self.held.len().min(buf.len());
+        let (size, status) = self.b.read_with_status(&mut buf[..len])?;
+        for (byte, a) in buf[..size].iter_mut().zip(self.held.drain(..size)) {
+            *byte = (self.f)(a, *byte);
+        }
+
+        if status.is_end() || (self.held.is_empty() && self.held_status.is_end()) {
+            self.ended = true;
+            self.held.clear();
+            Ok((size, Status::End))
+        } else if self.held.is_empty() {
+            Ok((size, self.held_status))
+        } else {
+            Ok((size, Status::active()))
+        }
+    }
+
+    #[inline]
+    fn minimum_buffer_size(&self) -> usize {
+        self.a
+            .minimum_buffer_size()
+            .max(self.b.minimum_buffer_size())
+    }
+}
+
+impl<A: ReadLayered, B: ReadLayered, F> Bufferable for CombineReader<A, B, F> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.a.abandon();
+        self.b.abandon();
+        self.held.clear();
+        self.ended = true;
+    }
+}
+
+impl<A: ReadLayered, B: ReadLayered, F: FnMut(u8, u8) -> u8> Read for CombineReader<A, B, F> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<A: fmt::Debug, B: fmt::Debug, F> fmt::Debug for CombineReader<A, B, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("CombineReader");
+        b.field("a", &self.a);
+        b.field("b", &self.b);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_combine_reader_xor() {
+    use crate::SliceReader;
+
+    let data = b"attack at dawn";
+    let key = b"0123456789abcdef";
+    let mut reader =
+        CombineReader::new(SliceReader::new(data), SliceReader::new(key), |a, b| a ^ b);
+    let mut masked = Vec::new();
+    reader.read_to_end(&mut masked).unwrap();
+    assert_eq!(masked.len(), data.len());
+    assert_ne!(&masked[..], &data[..]);
+
+    // XORing with the same keystream again recovers the input.
+    let mut reader =
+        CombineReader::new(SliceReader::new(&masked), SliceReader::new(key), |a, b| {
+            a ^ b
+        });
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "attack at dawn");
+}
+
+#[test]
+fn test_combine_reader_shorter_second() {
+    use crate::SliceReader;
+
+    let mut reader = CombineReader::new(
+        SliceReader::new(b"abcdef"),
+        SliceReader::new(&[1, 1, 1]),
+        u8::wrapping_add,
+    );
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, b"bcd");
+}
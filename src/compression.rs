@@ -0,0 +1,72 @@
+use std::io;
+
+/// A streaming compressor, for use with [`CodecWriter`].
+///
+/// [`CodecWriter`]: crate::CodecWriter
+pub trait Compressor {
+    /// Compress `input`, appending any compressed bytes which are ready to
+    /// `output`. The compressor may hold input back until more arrives.
+    fn compress(&mut self, input: &[u8], output: &mut Vec<u8>) -> io::Result<()>;
+
+    /// Append to `output` enough compressed bytes that a decompressor can
+    /// reproduce everything compressed so far, without ending the stream.
+    ///
+    /// The default implementation does nothing, which is only correct for
+    /// compressors which never hold input back.
+    fn sync_flush(&mut self, output: &mut Vec<u8>) -> io::Result<()> {
+        let _ = output;
+        Ok(())
+    }
+
+    /// End the compressed stream, appending any remaining compressed bytes
+    /// to `output`.
+    fn finish(&mut self, output: &mut Vec<u8>) -> io::Result<()>;
+}
+
+/// A streaming decompressor, for use with [`CodecReader`].
+///
+/// [`CodecReader`]: crate::CodecReader
+pub trait Decompressor {
+    /// Decompress a prefix of `input`, appending decompressed bytes to
+    /// `output`, and return the number of bytes of `input` consumed.
+    ///
+    /// Input which can't be decompressed until more arrives may be left
+    /// unconsumed; it's passed in again, with more appended, next time.
+    fn decompress(&mut self, input: &[u8], output: &mut Vec<u8>) -> io::Result<usize>;
+
+    /// Return whether the end of the compressed stream has been reached.
+    fn is_finished(&self) -> bool;
+}
+
+/// A codec which passes data through unchanged, for testing.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct IdentityCodec;
+
+impl IdentityCodec {
+    /// Construct a new `IdentityCodec`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Compressor for IdentityCodec {
+    fn compress(&mut self, input: &[u8], output: &mut Vec<u8>) -> io::Result<()> {
+        output.extend_from_slice(input);
+        Ok(())
+    }
+
+    fn finish(&mut self, _output: &mut Vec<u8>) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Decompressor for IdentityCodec {
+    fn decompress(&mut self, input: &[u8], output: &mut Vec<u8>) -> io::Result<usize> {
+        output.extend_from_slice(input);
+        Ok(input.len())
+    }
+
+    fn is_finished(&self) -> bool {
+        true
+    }
+}
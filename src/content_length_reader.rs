@@ -0,0 +1,167 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    ReadLayered, Status,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Adapts a `Read` to implement [`ReadLayered`], reporting `Status::End`
+/// after a known number of bytes.
+///
+/// This is useful for streams which don't end where their content does,
+/// such as a fixed-size HTTP body on a persistent connection. Reads are
+/// clamped so that nothing past the end of the content is read from the
+/// inner stream, which can then be recovered with
+/// [`ContentLengthReader::into_inner`] to read what follows.
+///
+/// If the inner stream ends before the content does, reads fail with
+/// [`io::ErrorKind::UnexpectedEof`].
+///
+/// This is the reading counterpart of [`ContentLengthWriter`].
+///
+/// [`ContentLengthWriter`]: crate::ContentLengthWriter
+pub struct ContentLengthReader<Inner> {
+    inner: Inner,
+    remaining: u64,
+}
+
+impl<Inner: Read> ContentLengthReader<Inner> {
+    /// Construct a new `ContentLengthReader` which reads `content_length`
+    /// bytes from `inner`.
+    pub fn new(inner: Inner, content_length: u64) -> Self {
+        Self {
+            inner,
+            remaining: content_length,
+        }
+    }
+
+    /// Return the number of bytes of content which haven't been read yet.
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Bytes read directly from the underlying reader aren't counted toward
+    /// the content length.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `ContentLengthReader` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+}
+
+impl<Inner: Read> ReadLayered for ContentLengthReader<Inner> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        if self.remaining == 0 {
+            return Ok((0, Status::End));
+        }
+        let max = buf
+            .len()
+            .min(self.remaining.min(usize::MAX as u64) as usize);
+        match self.inner.read(&mut buf[..max]) {
+            Ok(0) if max != 0 => {
+                self.abandon();
+                Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "stream ended before the content length",
+                ))
+            }
+            Ok(size) => {
+                self.remaining -= size as u64;
+                Ok((
+                    size,
+                    if self.remaining == 0 {
+                        Status::End
+                    } else {
+                        Status::active()
+                    },
+                ))
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => Ok((0, Status::active())),
+            Err(e) => {
+                self.abandon();
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<Inner> Bufferable for ContentLengthReader<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.remaining = 0;
+    }
+}
+
+impl<Inner: Read> Read for ContentLengthReader<Inner> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for ContentLengthReader<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("ContentLengthReader");
+        b.field("inner", &self.inner);
+        b.field("remaining", &self.remaining);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_content_length_reader() {
+    let mut reader = ContentLengthReader::new(io::Cursor::new(b"hello world"), 5);
+    let mut buf = [0; 16];
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (5, Status::End));
+    assert_eq!(&buf[..5], b"hello");
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (0, Status::End));
+
+    // Nothing past the content was read.
+    let inner = reader.into_inner();
+    assert_eq!(inner.position(), 5);
+}
+
+#[test]
+fn test_content_length_reader_short() {
+    let mut reader = ContentLengthReader::new(io::Cursor::new(b"hello"), 8);
+    let mut buf = [0; 4];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (4, Status::active())
+    );
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (1, Status::active())
+    );
+    assert_eq!(reader.remaining(), 3);
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap_err().kind(),
+        io::ErrorKind::UnexpectedEof
+    );
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (0, Status::End));
+}
@@ -0,0 +1,144 @@
+use crate::{Bufferable, WriteLayered};
+use std::fmt;
+use std::io::{self, Write};
+
+/// Adapts a [`WriteLayered`] to check that exactly a declared number of
+/// bytes are written.
+///
+/// This is the writing counterpart of [`ContentLengthReader`], and catches
+/// framing bugs where a body doesn't match its declared length. A write
+/// which would exceed the length fails, as does a [`WriteLayered::close`]
+/// before the length is reached; in both cases, the inner stream is
+/// abandoned.
+///
+/// [`ContentLengthReader`]: crate::ContentLengthReader
+pub struct ContentLengthWriter<Inner> {
+    inner: Inner,
+    remaining: u64,
+}
+
+impl<Inner: WriteLayered> ContentLengthWriter<Inner> {
+    /// Construct a new `ContentLengthWriter` which expects exactly
+    /// `content_length` bytes to be written to `inner`.
+    pub fn new(inner: Inner, content_length: u64) -> Self {
+        Self {
+            inner,
+            remaining: content_length,
+        }
+    }
+
+    /// Return the number of bytes which still need to be written.
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `ContentLengthWriter` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+}
+
+impl<Inner: WriteLayered> WriteLayered for ContentLengthWriter<Inner> {
+    fn close(&mut self) -> io::Result<()> {
+        if self.remaining != 0 {
+            self.abandon();
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "stream closed before the content length was reached",
+            ));
+        }
+        self.inner.close()
+    }
+}
+
+impl<Inner: WriteLayered> Bufferable for ContentLengthWriter<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: WriteLayered> Write for ContentLengthWriter<Inner> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.len() as u64 > self.remaining {
+            self.abandon();
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "write exceeds the content length",
+            ));
+        }
+        let size = self.inner.write(buf)?;
+        self.remaining -= size as u64;
+        Ok(size)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for ContentLengthWriter<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("ContentLengthWriter");
+        b.field("inner", &self.inner);
+        b.field("remaining", &self.remaining);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_content_length_writer_exact() {
+    use crate::CloseTrackingWriter;
+
+    let inner = CloseTrackingWriter::new(io::Cursor::new(Vec::new()));
+    let mut writer = ContentLengthWriter::new(inner, 11);
+    writer.write_all(b"hello ").unwrap();
+    assert_eq!(writer.remaining(), 5);
+    writer.write_all(b"world").unwrap();
+    writer.close().unwrap();
+    assert!(writer.get_ref().was_closed());
+    assert_eq!(writer.get_ref().get_ref().get_ref(), b"hello world");
+}
+
+#[test]
+fn test_content_length_writer_over() {
+    let mut writer = ContentLengthWriter::new(io::Cursor::new(Vec::new()), 4);
+    writer.write_all(b"abc").unwrap();
+    assert_eq!(
+        writer.write_all(b"de").unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+    assert_eq!(writer.get_ref().get_ref(), b"abc");
+}
+
+#[test]
+fn test_content_length_writer_under() {
+    use crate::CloseTrackingWriter;
+
+    let inner = CloseTrackingWriter::new(io::Cursor::new(Vec::new()));
+    let mut writer = ContentLengthWriter::new(inner, 4);
+    writer.write_all(b"abc").unwrap();
+    assert_eq!(
+        writer.close().unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+    assert!(!writer.get_ref().was_closed());
+}
@@ -0,0 +1,164 @@
+use crate::{default_suggested_buffer_size, ReadLayered, WriteLayered};
+use std::io;
+
+/// The largest scratch buffer [`copy`] allocates to satisfy suggested buffer
+/// sizes. Minimum buffer sizes are always satisfied.
+const MAX_COPY_BUFFER_SIZE: usize = 0x10_0000;
+
+/// Copy the entire contents of `reader` into `writer`, returning the number
+/// of bytes copied.
+///
+/// Like [`splice`], each read is followed by a
+/// [`WriteLayered::flush_with_status`] with the status of the read, so pushes
+/// are forwarded, and the end of `reader` closes `writer`.
+///
+/// The scratch buffer is sized from the larger of the endpoints' suggested
+/// buffer sizes, clamped to a maximum, and then raised to
+/// `reader.minimum_buffer_size()` if that's larger.
+///
+/// [`splice`]: crate::splice
+pub fn copy<R: ReadLayered + ?Sized, W: WriteLayered + ?Sized>(
+    reader: &mut R,
+    writer: &mut W,
+) -> io::Result<u64> {
+    let mut buf = vec![0; copy_buffer_size(reader, writer)];
+    let mut total = 0;
+    loop {
+        let (size, status) = match reader.read_with_status(&mut buf) {
+            Ok(size_and_status) => size_and_status,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        writer.write_all(&buf[..size])?;
+        writer.flush_with_status(status)?;
+        total += size as u64;
+        if status.is_end() {
+            return Ok(total);
+        }
+    }
+}
+
+/// Compute the size of the scratch buffer for copying from `reader` to
+/// `writer`.
+fn copy_buffer_size<R: ReadLayered + ?Sized, W: WriteLayered + ?Sized>(
+    reader: &R,
+    writer: &W,
+) -> usize {
+    let suggested = match reader
+        .suggested_buffer_size()
+        .max(writer.suggested_buffer_size())
+    {
+        0 => default_suggested_buffer_size(reader),
+        size => size.min(MAX_COPY_BUFFER_SIZE),
+    };
+    suggested.max(reader.minimum_buffer_size())
+}
+
+#[test]
+fn test_copy() {
+    use crate::{CloseTrackingWriter, SliceReader};
+
+    let mut reader = SliceReader::new(b"hello world");
+    let mut writer = CloseTrackingWriter::new(io::Cursor::new(Vec::new()));
+    assert_eq!(copy(&mut reader, &mut writer).unwrap(), 11);
+    assert!(writer.was_closed());
+    assert_eq!(writer.get_ref().get_ref(), b"hello world");
+}
+
+#[test]
+fn test_copy_large_minimum() {
+    use crate::{default_read, Bufferable, Status};
+    use std::io::Read;
+
+    const BLOCK: usize = 3 * MAX_COPY_BUFFER_SIZE;
+
+    // A reader which only reads whole blocks.
+    struct BlockReader(usize);
+
+    impl ReadLayered for BlockReader {
+        fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+            if buf.len() < BLOCK {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "buffer is smaller than a block",
+                ));
+            }
+            buf[..BLOCK].fill(0xa5);
+            self.0 -= 1;
+            Ok((
+                BLOCK,
+                if self.0 == 0 {
+                    Status::End
+                } else {
+                    Status::active()
+                },
+            ))
+        }
+
+        fn minimum_buffer_size(&self) -> usize {
+            BLOCK
+        }
+    }
+
+    impl Bufferable for BlockReader {
+        fn abandon(&mut self) {
+            self.0 = 0;
+        }
+    }
+
+    impl Read for BlockReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            default_read(self, buf)
+        }
+    }
+
+    let mut reader = BlockReader(2);
+    let mut writer = io::Cursor::new(Vec::new());
+    assert_eq!(copy_buffer_size(&reader, &writer), BLOCK);
+    assert_eq!(copy(&mut reader, &mut writer).unwrap(), 2 * BLOCK as u64);
+    assert_eq!(writer.get_ref().len(), 2 * BLOCK);
+}
+
+#[test]
+fn test_copy_buffer_size_clamped() {
+    use crate::{Bufferable, SliceReader};
+    use std::io::Write;
+
+    // A writer which suggests a huge buffer.
+    struct HungryWriter;
+
+    impl WriteLayered for HungryWriter {
+        fn close(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Bufferable for HungryWriter {
+        fn abandon(&mut self) {}
+
+        fn suggested_buffer_size(&self) -> usize {
+            usize::MAX
+        }
+    }
+
+    impl Write for HungryWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let reader = SliceReader::new(b"");
+    assert_eq!(
+        copy_buffer_size(&reader, &HungryWriter),
+        MAX_COPY_BUFFER_SIZE
+    );
+    let writer = io::Cursor::new(Vec::new());
+    assert_eq!(
+        copy_buffer_size(&reader, &writer),
+        default_suggested_buffer_size(&writer)
+    );
+}
@@ -0,0 +1,134 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored,
+    default_suggested_buffer_size, Bufferable, ReadLayered, Status,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Adapts a [`ReadLayered`] to count the bytes and pushes read from it, for
+/// example for progress reporting.
+///
+/// Data and statuses are passed through unchanged. For more detailed
+/// statistics, see [`StatsReader`].
+///
+/// [`StatsReader`]: crate::StatsReader
+pub struct CountingReader<Inner> {
+    inner: Inner,
+    bytes_read: u64,
+    push_count: u64,
+}
+
+impl<Inner: ReadLayered> CountingReader<Inner> {
+    /// Construct a new `CountingReader` which wraps `inner`.
+    pub fn new(inner: Inner) -> Self {
+        Self {
+            inner,
+            bytes_read: 0,
+            push_count: 0,
+        }
+    }
+
+    /// Return the number of bytes read so far.
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+
+    /// Return the number of reads so far which reported a push.
+    pub fn push_count(&self) -> u64 {
+        self.push_count
+    }
+
+    /// Consume this `CountingReader` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+}
+
+impl<Inner: ReadLayered> ReadLayered for CountingReader<Inner> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        let (size, status) = self.inner.read_with_status(buf)?;
+        self.bytes_read += size as u64;
+        if status.is_push() {
+            self.push_count += 1;
+        }
+        Ok((size, status))
+    }
+
+    #[inline]
+    fn minimum_buffer_size(&self) -> usize {
+        self.inner.minimum_buffer_size()
+    }
+}
+
+impl<Inner: ReadLayered> Bufferable for CountingReader<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        // Some streams, such as `SliceReader`, suggest 0, which
+        // `default_read_to_end` can't make progress with.
+        match self.inner.suggested_buffer_size() {
+            0 => default_suggested_buffer_size(self),
+            size => size,
+        }
+    }
+}
+
+impl<Inner: ReadLayered> Read for CountingReader<Inner> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for CountingReader<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("CountingReader");
+        b.field("inner", &self.inner);
+        b.field("bytes_read", &self.bytes_read);
+        b.field("push_count", &self.push_count);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_counting_reader() {
+    use crate::SliceReader;
+
+    let mut reader = CountingReader::new(SliceReader::new(b"hello world!"));
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "hello world!");
+    assert_eq!(reader.bytes_read(), 12);
+    assert_eq!(reader.push_count(), 0);
+}
+
+#[test]
+fn test_counting_reader_pushes() {
+    use crate::PacketReader;
+
+    let packets = vec![b"abc".to_vec(), b"de".to_vec(), b"f".to_vec()];
+    let mut reader = CountingReader::new(PacketReader::new(packets));
+    let mut buf = [0; 2];
+    while !reader.read_with_status(&mut buf).unwrap().1.is_end() {}
+    assert_eq!(reader.bytes_read(), 6);
+    assert_eq!(reader.push_count(), 2);
+}
@@ -0,0 +1,165 @@
+use crate::{Bufferable, WriteLayered};
+use std::fmt;
+use std::io::{self, IoSlice, Write};
+
+/// Adapts a [`WriteLayered`] to count the bytes written to it and the
+/// flushes and closes performed on it.
+///
+/// This is useful for testing that a buffering layer batches writes. A
+/// `flush_with_status(Status::push())` counts as a flush, and a
+/// `flush_with_status(Status::End)` as a close, by way of the default
+/// [`WriteLayered::flush_with_status`]. For more detailed statistics, see
+/// [`StatsWriter`].
+///
+/// [`StatsWriter`]: crate::StatsWriter
+pub struct CountingWriter<Inner> {
+    inner: Inner,
+    bytes_written: u64,
+    flush_count: u64,
+    close_count: u64,
+}
+
+impl<Inner: WriteLayered> CountingWriter<Inner> {
+    /// Construct a new `CountingWriter` which wraps `inner`.
+    pub fn new(inner: Inner) -> Self {
+        Self {
+            inner,
+            bytes_written: 0,
+            flush_count: 0,
+            close_count: 0,
+        }
+    }
+
+    /// Return the number of bytes written so far.
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
+    /// Return the number of flushes so far.
+    pub fn flush_count(&self) -> u64 {
+        self.flush_count
+    }
+
+    /// Return the number of closes so far.
+    pub fn close_count(&self) -> u64 {
+        self.close_count
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Operations performed directly on the underlying writer aren't
+    /// counted.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `CountingWriter` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+}
+
+impl<Inner: WriteLayered> WriteLayered for CountingWriter<Inner> {
+    #[inline]
+    fn close(&mut self) -> io::Result<()> {
+        self.close_count += 1;
+        self.inner.close()
+    }
+}
+
+impl<Inner: WriteLayered> Bufferable for CountingWriter<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: WriteLayered> Write for CountingWriter<Inner> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let size = self.inner.write(buf)?;
+        self.bytes_written += size as u64;
+        Ok(size)
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        let size = self.inner.write_vectored(bufs)?;
+        self.bytes_written += size as u64;
+        Ok(size)
+    }
+
+    #[cfg(can_vector)]
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.flush_count += 1;
+        self.inner.flush()
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for CountingWriter<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("CountingWriter");
+        b.field("inner", &self.inner);
+        b.field("bytes_written", &self.bytes_written);
+        b.field("flush_count", &self.flush_count);
+        b.field("close_count", &self.close_count);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_counting_writer() {
+    use crate::Status;
+
+    let mut writer = CountingWriter::new(io::Cursor::new(Vec::new()));
+    for chunk in [0_u8; 100].chunks(10) {
+        writer.write_all(chunk).unwrap();
+        writer.flush_with_status(Status::active()).unwrap();
+    }
+    writer.flush().unwrap();
+    writer.flush_with_status(Status::push()).unwrap();
+    let size = writer
+        .write_vectored(&[IoSlice::new(b"ab"), IoSlice::new(b"cd")])
+        .unwrap();
+    assert_eq!(size, 4);
+    writer.flush_with_status(Status::End).unwrap();
+    assert!(writer.bytes_written() >= 102);
+    assert_eq!(
+        writer.bytes_written(),
+        writer.get_ref().get_ref().len() as u64
+    );
+    assert_eq!(writer.flush_count(), 2);
+    assert_eq!(writer.close_count(), 1);
+}
+
+#[test]
+fn test_counting_writer_batching() {
+    use crate::BufWriterLayered;
+
+    // A buffering layer turns ten writes into one.
+    let inner = CountingWriter::new(io::Cursor::new(Vec::new()));
+    let mut writer = BufWriterLayered::with_capacity(128, inner);
+    for chunk in [0_u8; 100].chunks(10) {
+        writer.write_all(chunk).unwrap();
+    }
+    assert_eq!(writer.get_ref().bytes_written(), 0);
+    writer.flush().unwrap();
+    assert_eq!(writer.get_ref().bytes_written(), 100);
+    assert_eq!(writer.get_ref().flush_count(), 1);
+}
@@ -0,0 +1,229 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    ReadLayered, Status,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read, Write};
+
+/// The number of bytes per line of hexdump used by [`DebugReader::new`] and
+/// [`DebugWriter::new`].
+///
+/// [`DebugWriter::new`]: crate::DebugWriter::new
+pub(crate) const DEFAULT_BYTES_PER_LINE: usize = 16;
+
+/// Adapts a [`ReadLayered`] to write a hexdump of each chunk of data read
+/// from it to a sink, such as `io::stderr()` or a file, for debugging.
+///
+/// Each read is described by a line with the number of bytes read and the
+/// status, or the error, followed by a hexdump of the bytes read, with
+/// offsets counted from the start of the stream. Peeks are dumped the same
+/// way, and abandoning the stream is noted. Data and statuses are passed
+/// through unchanged. Errors writing to the sink are ignored, so that they
+/// don't disturb the stream being debugged.
+///
+/// Unlike `LoggingReader`, this doesn't depend on a logger, and dumps the
+/// data itself, which is convenient for capturing to a file.
+pub struct DebugReader<Inner, Out> {
+    inner: Inner,
+    out: Out,
+    bytes_per_line: usize,
+    position: u64,
+}
+
+impl<Inner: ReadLayered, Out: Write> DebugReader<Inner, Out> {
+    /// Construct a new `DebugReader` which wraps `inner` and writes
+    /// hexdumps with 16 bytes per line to `out`.
+    pub fn new(inner: Inner, out: Out) -> Self {
+        Self::with_bytes_per_line(inner, out, DEFAULT_BYTES_PER_LINE)
+    }
+
+    /// Construct a new `DebugReader` which wraps `inner` and writes
+    /// hexdumps with `bytes_per_line` bytes per line to `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes_per_line` is zero.
+    pub fn with_bytes_per_line(inner: Inner, out: Out, bytes_per_line: usize) -> Self {
+        assert_ne!(bytes_per_line, 0, "bytes per line must be non-zero");
+        Self {
+            inner,
+            out,
+            bytes_per_line,
+            position: 0,
+        }
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Reads made directly on the underlying reader aren't dumped.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `DebugReader` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+
+    fn dump_result(
+        &mut self,
+        op: &str,
+        buf: &[u8],
+        result: &io::Result<(usize, Status)>,
+    ) -> io::Result<()> {
+        match result {
+            Ok((size, status)) => {
+                writeln!(
+                    self.out,
+                    "{}: {} of {} bytes, {:?}",
+                    op,
+                    size,
+                    buf.len(),
+                    status
+                )?;
+                hexdump(
+                    &mut self.out,
+                    self.position,
+                    &buf[..*size],
+                    self.bytes_per_line,
+                )
+            }
+            Err(e) => writeln!(self.out, "{}: error: {}", op, e),
+        }
+    }
+}
+
+impl<Inner: ReadLayered, Out: Write> ReadLayered for DebugReader<Inner, Out> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        let result = self.inner.read_with_status(buf);
+        self.dump_result("read_with_status", buf, &result).ok();
+        if let Ok((size, _)) = result {
+            self.position += size as u64;
+        }
+        result
+    }
+
+    fn peek(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        let result = self.inner.peek(buf);
+        self.dump_result("peek", buf, &result).ok();
+        result
+    }
+
+    #[inline]
+    fn minimum_buffer_size(&self) -> usize {
+        self.inner.minimum_buffer_size()
+    }
+}
+
+impl<Inner: ReadLayered, Out: Write> Bufferable for DebugReader<Inner, Out> {
+    #[inline]
+    fn abandon(&mut self) {
+        writeln!(self.out, "abandon").ok();
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: ReadLayered, Out: Write> Read for DebugReader<Inner, Out> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug, Out> fmt::Debug for DebugReader<Inner, Out> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("DebugReader");
+        b.field("inner", &self.inner);
+        b.field("bytes_per_line", &self.bytes_per_line);
+        b.field("position", &self.position);
+        b.finish()
+    }
+}
+
+/// Write a hexdump of `data`, which starts at offset `position` in its
+/// stream, to `out`, with `bytes_per_line` bytes per line.
+///
+/// Each line has the offset, the bytes in hex, and the bytes as ASCII, with
+/// `.` for bytes which aren't printable.
+pub(crate) fn hexdump<Out: Write + ?Sized>(
+    out: &mut Out,
+    position: u64,
+    data: &[u8],
+    bytes_per_line: usize,
+) -> io::Result<()> {
+    let mut offset = position;
+    for line in data.chunks(bytes_per_line) {
+        write!(out, "{:08x} ", offset)?;
+        for i in 0..bytes_per_line {
+            match line.get(i) {
+                Some(byte) => write!(out, " {:02x}", byte)?,
+                None => write!(out, "   ")?,
+            }
+        }
+        let ascii: String = line
+            .iter()
+            .map(|&byte| {
+                if byte.is_ascii_graphic() || byte == b' ' {
+                    char::from(byte)
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        writeln!(out, "  |{}|", ascii)?;
+        offset += line.len() as u64;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_debug_reader() {
+    use crate::SliceReader;
+
+    let mut out = Vec::new();
+    let mut reader = DebugReader::with_bytes_per_line(SliceReader::new(b"hello\n!"), &mut out, 4);
+    let mut buf = [0; 6];
+    assert_eq!(reader.peek(&mut buf[..2]).unwrap(), (2, Status::active()));
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (6, Status::active())
+    );
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (1, Status::End));
+    reader.abandon();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "peek: 2 of 2 bytes, Open(Active)\n\
+         00000000  68 65        |he|\n\
+         read_with_status: 6 of 6 bytes, Open(Active)\n\
+         00000000  68 65 6c 6c  |hell|\n\
+         00000004  6f 0a        |o.|\n\
+         read_with_status: 1 of 6 bytes, End\n\
+         00000006  21           |!|\n\
+         abandon\n"
+    );
+}
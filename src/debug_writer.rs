@@ -0,0 +1,159 @@
+use crate::debug_reader::{hexdump, DEFAULT_BYTES_PER_LINE};
+use crate::{Bufferable, Status, WriteLayered};
+use std::fmt;
+use std::io::{self, Write};
+
+/// Adapts a [`WriteLayered`] to write a hexdump of each chunk of data
+/// written to it to a sink, such as `io::stderr()` or a file, for debugging.
+///
+/// Each write is described by a line with the number of bytes written, or
+/// the error, followed by a hexdump of the bytes written, with offsets
+/// counted from the start of the stream. Flushes, with their status, and
+/// closing and abandoning the stream are noted. Data and statuses are
+/// passed through unchanged. Errors writing to the sink are ignored, so that
+/// they don't disturb the stream being debugged.
+pub struct DebugWriter<Inner, Out> {
+    inner: Inner,
+    out: Out,
+    bytes_per_line: usize,
+    position: u64,
+}
+
+impl<Inner: WriteLayered, Out: Write> DebugWriter<Inner, Out> {
+    /// Construct a new `DebugWriter` which wraps `inner` and writes
+    /// hexdumps with 16 bytes per line to `out`.
+    pub fn new(inner: Inner, out: Out) -> Self {
+        Self::with_bytes_per_line(inner, out, DEFAULT_BYTES_PER_LINE)
+    }
+
+    /// Construct a new `DebugWriter` which wraps `inner` and writes
+    /// hexdumps with `bytes_per_line` bytes per line to `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes_per_line` is zero.
+    pub fn with_bytes_per_line(inner: Inner, out: Out, bytes_per_line: usize) -> Self {
+        assert_ne!(bytes_per_line, 0, "bytes per line must be non-zero");
+        Self {
+            inner,
+            out,
+            bytes_per_line,
+            position: 0,
+        }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Writes made directly to the underlying writer aren't dumped.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `DebugWriter` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+
+    fn dump_write(&mut self, buf: &[u8], result: &io::Result<usize>) -> io::Result<()> {
+        match result {
+            Ok(size) => {
+                writeln!(self.out, "write: {} of {} bytes", size, buf.len())?;
+                hexdump(
+                    &mut self.out,
+                    self.position,
+                    &buf[..*size],
+                    self.bytes_per_line,
+                )
+            }
+            Err(e) => writeln!(self.out, "write: error: {}", e),
+        }
+    }
+
+    fn dump_unit(&mut self, op: fmt::Arguments<'_>, result: &io::Result<()>) {
+        match result {
+            Ok(()) => writeln!(self.out, "{}", op),
+            Err(e) => writeln!(self.out, "{}: error: {}", op, e),
+        }
+        .ok();
+    }
+}
+
+impl<Inner: WriteLayered, Out: Write> WriteLayered for DebugWriter<Inner, Out> {
+    fn close(&mut self) -> io::Result<()> {
+        let result = self.inner.close();
+        self.dump_unit(format_args!("close"), &result);
+        result
+    }
+
+    fn flush_with_status(&mut self, status: Status) -> io::Result<()> {
+        let result = self.inner.flush_with_status(status);
+        self.dump_unit(format_args!("flush_with_status({:?})", status), &result);
+        result
+    }
+}
+
+impl<Inner: WriteLayered, Out: Write> Bufferable for DebugWriter<Inner, Out> {
+    #[inline]
+    fn abandon(&mut self) {
+        writeln!(self.out, "abandon").ok();
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: WriteLayered, Out: Write> Write for DebugWriter<Inner, Out> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let result = self.inner.write(buf);
+        self.dump_write(buf, &result).ok();
+        if let Ok(size) = result {
+            self.position += size as u64;
+        }
+        result
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let result = self.inner.flush();
+        self.dump_unit(format_args!("flush"), &result);
+        result
+    }
+}
+
+impl<Inner: fmt::Debug, Out> fmt::Debug for DebugWriter<Inner, Out> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("DebugWriter");
+        b.field("inner", &self.inner);
+        b.field("bytes_per_line", &self.bytes_per_line);
+        b.field("position", &self.position);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_debug_writer() {
+    let mut out = Vec::new();
+    let mut writer = DebugWriter::with_bytes_per_line(io::Cursor::new(Vec::new()), &mut out, 4);
+    writer.write_all(b"hello\n").unwrap();
+    writer.flush_with_status(Status::push()).unwrap();
+    writer.write_all(b"!").unwrap();
+    writer.close().unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"hello\n!");
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "write: 6 of 6 bytes\n\
+         00000000  68 65 6c 6c  |hell|\n\
+         00000004  6f 0a        |o.|\n\
+         flush_with_status(Open(Push))\n\
+         write: 1 of 1 bytes\n\
+         00000006  21           |!|\n\
+         close\n"
+    );
+}
@@ -1,5 +1,9 @@
-use crate::{ReadLayered, WriteLayered};
+use crate::{default_suggested_buffer_size, ReadLayered, WriteLayered};
+#[cfg(feature = "futures-io")]
+use crate::{AsyncReadLayered, AsyncWriteLayered};
 use duplex::{Duplex, HalfDuplex};
+use std::io;
+use std::{panic, thread};
 
 /// A trait which simply combines [`ReadLayered`], [`WriteLayered`], and
 /// [`HalfDuplex`].
@@ -7,4 +11,153 @@ pub trait HalfDuplexLayered: HalfDuplex + ReadLayered + WriteLayered {}
 
 impl<T: Duplex + ReadLayered + WriteLayered> HalfDuplexLayered for T {}
 
-// TODO: `AsyncReadLayered` and `AsyncWriteLayered`?
+/// A trait which simply combines [`AsyncReadLayered`], [`AsyncWriteLayered`],
+/// and [`Duplex`].
+///
+/// This is the async counterpart of [`HalfDuplexLayered`]. It's bounded by
+/// `Duplex` rather than `HalfDuplex`, because `HalfDuplex` requires the
+/// synchronous `Read` and `Write`.
+#[cfg(feature = "futures-io")]
+pub trait AsyncHalfDuplexLayered: Duplex + AsyncReadLayered + AsyncWriteLayered {}
+
+#[cfg(feature = "futures-io")]
+impl<T: Duplex + AsyncReadLayered + AsyncWriteLayered> AsyncHalfDuplexLayered for T {}
+
+/// Pump data in both directions between two endpoints, given as read and
+/// write halves such as those returned by [`LayeredDuplexer::split`], until
+/// both directions have ended.
+///
+/// Data read from `a_read` is written to `b_write`, and data read from
+/// `b_read` is written to `a_write`, each followed by a
+/// [`WriteLayered::flush_with_status`] with the status of the read, so a push
+/// on one side flushes the other, and the end of one side's input closes the
+/// other side's output.
+///
+/// Each direction is pumped on its own thread, so a read which blocks in one
+/// direction doesn't hold up the other. If one direction fails, the other
+/// still runs until it ends, and then the error is returned.
+///
+/// [`LayeredDuplexer::split`]: crate::LayeredDuplexer::split
+pub fn splice<AR, AW, BR, BW>(
+    a_read: &mut AR,
+    a_write: &mut AW,
+    b_read: &mut BR,
+    b_write: &mut BW,
+) -> io::Result<()>
+where
+    AR: ReadLayered + Send + ?Sized,
+    AW: WriteLayered + Send + ?Sized,
+    BR: ReadLayered + Send + ?Sized,
+    BW: WriteLayered + Send + ?Sized,
+{
+    thread::scope(|scope| {
+        let a_to_b = scope.spawn(|| pump(a_read, b_write));
+        let b_to_a = pump(b_read, a_write);
+        let a_to_b = a_to_b
+            .join()
+            .unwrap_or_else(|payload| panic::resume_unwind(payload));
+        a_to_b.and(b_to_a)
+    })
+}
+
+/// Forward data and statuses from `from` to `to` until `from` ends.
+fn pump<R: ReadLayered + ?Sized, W: WriteLayered + ?Sized>(
+    from: &mut R,
+    to: &mut W,
+) -> io::Result<()> {
+    let size = default_suggested_buffer_size(from).max(from.minimum_buffer_size());
+    let mut buf = vec![0; size];
+    loop {
+        let (size, status) = from.read_with_status(&mut buf)?;
+        to.write_all(&buf[..size])?;
+        to.flush_with_status(status)?;
+        if status.is_end() {
+            return Ok(());
+        }
+    }
+}
+
+#[test]
+fn test_splice() {
+    use crate::{LayeredReader, LayeredWriter};
+    use std::io::Write;
+
+    // A writer which records what's written to it, with `|` marking each
+    // flush.
+    struct Recorder<'a>(&'a mut Vec<u8>);
+
+    impl Write for Recorder<'_> {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.0.push(b'|');
+            Ok(())
+        }
+    }
+
+    let mut a_output = Vec::new();
+    let mut b_output = Vec::new();
+    let mut a_read = LayeredReader::line_by_line(io::Cursor::new(b"ping\n"));
+    let mut a_write = LayeredWriter::new(Recorder(&mut a_output));
+    let mut b_read = LayeredReader::new(io::Cursor::new(b"pong"));
+    let mut b_write = LayeredWriter::new(Recorder(&mut b_output));
+
+    splice(&mut a_read, &mut a_write, &mut b_read, &mut b_write).unwrap();
+    drop((a_write, b_write));
+
+    // `a`'s line is pushed, then its end closes `b`'s output.
+    assert_eq!(b_output, b"ping\n||");
+    assert_eq!(a_output, b"pong|");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_splice_server_first() {
+    use crate::LayeredDuplexer;
+    use std::io::{Read, Write};
+    use std::net::Shutdown;
+    use std::os::unix::net::UnixStream;
+
+    let (a, mut client) = UnixStream::pair().unwrap();
+    let (b, mut server) = UnixStream::pair().unwrap();
+    let (mut a_read, mut a_write) = LayeredDuplexer::with_half_close(a).split();
+    let (mut b_read, mut b_write) = LayeredDuplexer::with_half_close(b).split();
+    let splicer = thread::spawn(move || {
+        splice(&mut a_read, &mut a_write, &mut b_read, &mut b_write).unwrap();
+    });
+
+    // The server speaks first, while the client is idle.
+    server.write_all(b"hello").unwrap();
+    let mut greeting = [0; 5];
+    client.read_exact(&mut greeting).unwrap();
+    assert_eq!(&greeting, b"hello");
+
+    client.write_all(b"ping").unwrap();
+    client.shutdown(Shutdown::Write).unwrap();
+    let mut request = Vec::new();
+    server.read_to_end(&mut request).unwrap();
+    assert_eq!(request, b"ping");
+
+    server.write_all(b"pong").unwrap();
+    server.shutdown(Shutdown::Write).unwrap();
+    let mut response = Vec::new();
+    client.read_to_end(&mut response).unwrap();
+    assert_eq!(response, b"pong");
+
+    splicer.join().unwrap();
+}
+
+#[cfg(feature = "futures-io")]
+#[test]
+fn test_async_half_duplex_layered_bounds() {
+    fn takes_async_half_duplex_layered(_stream: impl AsyncHalfDuplexLayered) {}
+
+    // This only needs to compile; it checks that the blanket impl covers
+    // every type with the component traits.
+    #[allow(dead_code)]
+    fn forwards<T: Duplex + AsyncReadLayered + AsyncWriteLayered>(stream: T) {
+        takes_async_half_duplex_layered(stream)
+    }
+}
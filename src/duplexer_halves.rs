@@ -0,0 +1,286 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    ReadLayered, Status, WriteLayered,
+};
+use std::error::Error;
+use std::fmt;
+use std::io::{self, IoSlice, IoSliceMut, Read, Write};
+use std::sync::Arc;
+
+/// The read half of a [`LayeredDuplexer`], returned by
+/// [`LayeredDuplexer::split`].
+///
+/// Reads go through a shared reference to the inner stream, so they don't
+/// wait for the write half. Abandoning this half ends its direction; the
+/// inner stream is dropped once both halves have been dropped.
+///
+/// [`LayeredDuplexer`]: crate::LayeredDuplexer
+/// [`LayeredDuplexer::split`]: crate::LayeredDuplexer::split
+pub struct ReadHalf<Inner> {
+    pub(crate) inner: Arc<Inner>,
+    pub(crate) eos_as_push: bool,
+    pub(crate) line_by_line: bool,
+    pub(crate) ended: bool,
+}
+
+/// The write half of a [`LayeredDuplexer`], returned by
+/// [`LayeredDuplexer::split`].
+///
+/// Writes go through a shared reference to the inner stream, so they don't
+/// wait for the read half. [`WriteLayered::close`] flushes and closes only
+/// the write direction. If the `LayeredDuplexer` was constructed with
+/// [`LayeredDuplexer::with_half_close`], this shuts down the inner stream's
+/// write direction, so the peer sees the end of the stream; otherwise the
+/// peer doesn't observe it until the inner stream is dropped, once both
+/// halves have been dropped.
+///
+/// Like [`LayeredWriter`], this panics on drop if it wasn't closed or
+/// abandoned.
+///
+/// [`LayeredDuplexer`]: crate::LayeredDuplexer
+/// [`LayeredDuplexer::split`]: crate::LayeredDuplexer::split
+/// [`LayeredDuplexer::with_half_close`]: crate::LayeredDuplexer::with_half_close
+/// [`LayeredWriter`]: crate::LayeredWriter
+pub struct WriteHalf<Inner> {
+    pub(crate) inner: Arc<Inner>,
+    pub(crate) shutdown_write: Option<fn(&Inner) -> io::Result<()>>,
+    pub(crate) closed: bool,
+}
+
+/// The error returned by [`LayeredDuplexer::reunite`] when the halves come
+/// from different streams. It holds the halves, so they can be recovered.
+///
+/// [`LayeredDuplexer::reunite`]: crate::LayeredDuplexer::reunite
+pub struct ReuniteError<Inner>(pub ReadHalf<Inner>, pub WriteHalf<Inner>);
+
+impl<Inner> ReadHalf<Inner>
+where
+    for<'a> &'a Inner: Read,
+{
+    /// Gets a reference to the underlying stream.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+}
+
+impl<Inner> ReadLayered for ReadHalf<Inner>
+where
+    for<'a> &'a Inner: Read,
+{
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        if self.ended {
+            return Ok((0, Status::End));
+        }
+        match (&*self.inner).read(buf) {
+            Ok(0) if !buf.is_empty() => {
+                if self.eos_as_push {
+                    Ok((0, Status::push()))
+                } else {
+                    self.ended = true;
+                    Ok((0, Status::End))
+                }
+            }
+            Ok(size) => {
+                if self.line_by_line && size != 0 && buf[size - 1] == b'\n' {
+                    Ok((size, Status::push()))
+                } else {
+                    Ok((size, Status::active()))
+                }
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => Ok((0, Status::active())),
+            Err(e) => {
+                self.abandon();
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<Inner> Bufferable for ReadHalf<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.ended = true;
+    }
+}
+
+impl<Inner> Read for ReadHalf<Inner>
+where
+    for<'a> &'a Inner: Read,
+{
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner> WriteHalf<Inner>
+where
+    for<'a> &'a Inner: Write,
+{
+    /// Gets a reference to the underlying stream.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Return the inner stream for a write, or fail if the write direction
+    /// has ended.
+    fn open(&self) -> io::Result<&Inner> {
+        if self.closed {
+            Err(stream_already_ended())
+        } else {
+            Ok(&self.inner)
+        }
+    }
+}
+
+impl<Inner> WriteLayered for WriteHalf<Inner>
+where
+    for<'a> &'a Inner: Write,
+{
+    fn close(&mut self) -> io::Result<()> {
+        let mut inner = self.open()?;
+        let result = match self.shutdown_write {
+            Some(shutdown_write) => inner.flush().and_then(|()| shutdown_write(inner)),
+            None => inner.flush(),
+        };
+        self.closed = true;
+        result
+    }
+}
+
+impl<Inner> Bufferable for WriteHalf<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.closed = true;
+    }
+}
+
+impl<Inner> Write for WriteHalf<Inner>
+where
+    for<'a> &'a Inner: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.open()?.write(buf).map_err(|e| {
+            self.abandon();
+            e
+        })
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.open()?.write_vectored(bufs).map_err(|e| {
+            self.abandon();
+            e
+        })
+    }
+
+    #[cfg(can_vector)]
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        !self.closed && (&*self.inner).is_write_vectored()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.open()?.flush().map_err(|e| {
+            self.abandon();
+            e
+        })
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for ReadHalf<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("ReadHalf");
+        b.field("inner", &self.inner);
+        b.field("ended", &self.ended);
+        b.finish()
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for WriteHalf<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("WriteHalf");
+        b.field("inner", &self.inner);
+        b.field("closed", &self.closed);
+        b.finish()
+    }
+}
+
+impl<Inner> fmt::Debug for ReuniteError<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ReuniteError(..)")
+    }
+}
+
+impl<Inner> fmt::Display for ReuniteError<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("tried to reunite halves that are not from the same stream")
+    }
+}
+
+impl<Inner> Error for ReuniteError<Inner> {}
+
+impl<Inner> Drop for WriteHalf<Inner> {
+    fn drop(&mut self) {
+        assert!(self.closed, "stream was not closed or abandoned");
+    }
+}
+
+fn stream_already_ended() -> io::Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, "stream has already ended")
+}
+
+#[cfg(unix)]
+#[test]
+fn test_write_half_close() {
+    use crate::{default_write_all_vectored, LayeredDuplexer};
+    use std::os::unix::net::UnixStream;
+
+    let (local, mut peer) = UnixStream::pair().unwrap();
+    let (mut read, mut write) = LayeredDuplexer::with_half_close(local).split();
+    let mut bufs = [IoSlice::new(b"pi"), IoSlice::new(b"ng")];
+    default_write_all_vectored(&mut write, &mut bufs).unwrap();
+    write.close().unwrap();
+
+    // The peer sees the end of the stream, and can still respond.
+    let mut request = Vec::new();
+    peer.read_to_end(&mut request).unwrap();
+    assert_eq!(request, b"ping");
+    peer.write_all(b"pong").unwrap();
+    drop(peer);
+
+    let mut s = String::new();
+    read.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "pong");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_read_half_line_by_line_empty() {
+    use crate::LayeredDuplexer;
+    use std::os::unix::net::UnixStream;
+
+    // Give the read something to return, so that it doesn't block.
+    let (local, mut peer) = UnixStream::pair().unwrap();
+    peer.write_all(b"\n").unwrap();
+    let (mut read, mut write) = LayeredDuplexer::line_by_line(local).split();
+    assert_eq!(
+        read.read_with_status(&mut []).unwrap(),
+        (0, Status::active())
+    );
+    write.abandon();
+}
@@ -0,0 +1,120 @@
+use crate::{Bufferable, WriteLayered};
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use std::fmt;
+use std::io::{self, Write};
+
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
+/// Adapts a [`WriteLayered`] to encrypt the data written to it with AES-128
+/// in CTR mode.
+///
+/// This is the counterpart of [`DecryptReader`]. CTR mode is
+/// length-preserving, so nothing is buffered and there's no trailer to write
+/// on [`WriteLayered::close`]. Each write is encrypted and written to the
+/// inner stream in full, so that the position in the keystream always
+/// matches the amount of data written.
+///
+/// [`DecryptReader`]: crate::DecryptReader
+pub struct EncryptWriter<Inner> {
+    inner: Inner,
+    cipher: Aes128Ctr,
+    out: Vec<u8>,
+}
+
+impl<Inner: WriteLayered> EncryptWriter<Inner> {
+    /// Construct a new `EncryptWriter` which wraps `inner` and encrypts with
+    /// `key`, starting with the counter `iv`.
+    pub fn new(inner: Inner, key: &[u8; 16], iv: &[u8; 16]) -> Self {
+        Self {
+            inner,
+            cipher: Aes128Ctr::new(key.into(), iv.into()),
+            out: Vec::new(),
+        }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `EncryptWriter` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+}
+
+impl<Inner: WriteLayered> WriteLayered for EncryptWriter<Inner> {
+    #[inline]
+    fn close(&mut self) -> io::Result<()> {
+        self.inner.close()
+    }
+}
+
+impl<Inner: WriteLayered> Bufferable for EncryptWriter<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.out = Vec::new();
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: WriteLayered> Write for EncryptWriter<Inner> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.out.clear();
+        self.out.extend_from_slice(buf);
+        self.cipher.apply_keystream(&mut self.out);
+        self.inner.write_all(&self.out)?;
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for EncryptWriter<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Don't print the cipher state, which would reveal the key.
+        let mut b = f.debug_struct("EncryptWriter");
+        b.field("inner", &self.inner);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_encrypt_writer_round_trip() {
+    use crate::{DecryptReader, IntervalPushReader, SliceReader, Status};
+    use std::io::Read;
+
+    let key = *b"0123456789abcdef";
+    let iv = [7; 16];
+    let mut writer = EncryptWriter::new(io::Cursor::new(Vec::new()), &key, &iv);
+    writer.write_all(b"attack ").unwrap();
+    writer.flush_with_status(Status::push()).unwrap();
+    writer.write_all(b"at dawn, or shortly thereafter").unwrap();
+    writer.close().unwrap();
+    let ciphertext = writer.get_ref().get_ref();
+    assert_eq!(ciphertext.len(), 37);
+    assert!(!ciphertext.starts_with(b"attack"));
+
+    // Read the ciphertext back in pieces which don't line up with the writes
+    // or with AES blocks.
+    let inner = IntervalPushReader::new(SliceReader::new(ciphertext), 5);
+    let mut reader = DecryptReader::new(inner, &key, &iv);
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "attack at dawn, or shortly thereafter");
+}
@@ -0,0 +1,219 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    ReadLayered, Status,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Adapts a [`ReadLayered`] to return fixed-size records.
+///
+/// Input is buffered until a whole record is available, and the end of each
+/// record is reported as a `Status::push()`. Once the inner stream has ended
+/// and every record has been returned, `Status::End` is reported. If the
+/// inner stream ends partway through a record, the read fails with
+/// `io::ErrorKind::InvalidData`, unless the reader was constructed with
+/// [`FixedRecordReader::allowing_partial`], in which case the partial record
+/// is returned as the last record.
+pub struct FixedRecordReader<Inner> {
+    inner: Inner,
+    record_size: usize,
+    allow_partial: bool,
+    record: Vec<u8>,
+    record_pos: usize,
+    in_record: bool,
+    inner_ended: bool,
+}
+
+impl<Inner: ReadLayered> FixedRecordReader<Inner> {
+    /// Construct a new `FixedRecordReader` which wraps `inner` and returns
+    /// records of `record_size` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `record_size` is zero.
+    pub fn new(inner: Inner, record_size: usize) -> Self {
+        assert!(record_size != 0, "record size must be non-zero");
+        Self {
+            inner,
+            record_size,
+            allow_partial: false,
+            record: Vec::with_capacity(record_size),
+            record_pos: 0,
+            in_record: false,
+            inner_ended: false,
+        }
+    }
+
+    /// Like [`FixedRecordReader::new`], but a partial record at the end of
+    /// the stream is returned rather than treated as an error.
+    pub fn allowing_partial(inner: Inner, record_size: usize) -> Self {
+        Self {
+            allow_partial: true,
+            ..Self::new(inner, record_size)
+        }
+    }
+
+    /// Consume this `FixedRecordReader` and return the inner stream.
+    ///
+    /// Any buffered partial record is discarded.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+
+    /// Read from the inner stream until a record is complete. Return `Some`
+    /// status if no record is available yet.
+    fn fill_record(&mut self) -> io::Result<Option<Status>> {
+        while self.record.len() < self.record_size && !self.inner_ended {
+            let start = self.record.len();
+            self.record.resize(self.record_size, 0);
+            let result = self.inner.read_with_status(&mut self.record[start..]);
+            let (size, status) = match result {
+                Ok(size_and_status) => size_and_status,
+                Err(e) => {
+                    self.record.truncate(start);
+                    return Err(e);
+                }
+            };
+            self.record.truncate(start + size);
+            if status.is_end() {
+                self.inner_ended = true;
+            } else if size == 0 {
+                return Ok(Some(Status::active()));
+            }
+        }
+
+        if self.record.is_empty() {
+            return Ok(Some(Status::End));
+        }
+        if self.record.len() < self.record_size && !self.allow_partial {
+            self.abandon();
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "stream ended partway through a record",
+            ));
+        }
+        self.record_pos = 0;
+        self.in_record = true;
+        Ok(None)
+    }
+}
+
+impl<Inner: ReadLayered> ReadLayered for FixedRecordReader<Inner> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        if !self.in_record {
+            if let Some(status) = self.fill_record()? {
+                return Ok((0, status));
+            }
+        }
+
+        let size = (self.record.len() - self.record_pos).min(buf.len());
+        buf[..size].copy_from_slice(&self.record[self.record_pos..self.record_pos + size]);
+        self.record_pos += size;
+        if self.record_pos == self.record.len() {
+            self.record.clear();
+            self.in_record = false;
+            Ok((size, Status::push()))
+        } else {
+            Ok((size, Status::active()))
+        }
+    }
+
+    #[inline]
+    fn minimum_buffer_size(&self) -> usize {
+        self.inner.minimum_buffer_size()
+    }
+}
+
+impl<Inner: ReadLayered> Bufferable for FixedRecordReader<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon();
+        self.record.clear();
+        self.in_record = false;
+        self.inner_ended = true;
+    }
+}
+
+impl<Inner: ReadLayered> Read for FixedRecordReader<Inner> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for FixedRecordReader<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("FixedRecordReader");
+        b.field("inner", &self.inner);
+        b.field("record_size", &self.record_size);
+        b.field("allow_partial", &self.allow_partial);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_fixed_record_reader() {
+    use crate::IntervalPushReader;
+
+    // Deliver the input in pieces which don't line up with the records.
+    let inner = IntervalPushReader::new(crate::SliceReader::new(b"record01record02record03"), 5);
+    let mut reader = FixedRecordReader::new(inner, 8);
+    let mut buf = [0; 16];
+    for expected in [b"record01", b"record02", b"record03"] {
+        assert_eq!(
+            reader.read_with_status(&mut buf).unwrap(),
+            (8, Status::push())
+        );
+        assert_eq!(&buf[..8], expected);
+    }
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (0, Status::End));
+}
+
+#[test]
+fn test_fixed_record_reader_small_buffer() {
+    let mut reader = FixedRecordReader::new(crate::SliceReader::new(b"abcdefgh"), 8);
+    let mut buf = [0; 5];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (5, Status::active())
+    );
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (3, Status::push())
+    );
+    assert_eq!(&buf[..3], b"fgh");
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (0, Status::End));
+}
+
+#[test]
+fn test_fixed_record_reader_partial() {
+    use crate::SliceReader;
+
+    let mut reader = FixedRecordReader::new(SliceReader::new(b"12345678abc"), 8);
+    let mut buf = [0; 8];
+    reader.read_with_status(&mut buf).unwrap();
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+
+    let mut reader = FixedRecordReader::allowing_partial(SliceReader::new(b"12345678abc"), 8);
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "12345678abc");
+}
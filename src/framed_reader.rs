@@ -0,0 +1,155 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored,
+    default_suggested_buffer_size, Bufferable, Decoder, ReadLayered, Status,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Adapts a [`ReadLayered`] to decode frames using a [`Decoder`].
+///
+/// The contents of each frame are returned in order, with the end of each
+/// frame reported as a `Status::push()`. Once the inner stream has ended and
+/// every frame has been returned, `Status::End` is reported.
+pub struct FramedReader<Inner, D> {
+    inner: Inner,
+    decoder: D,
+    src: Vec<u8>,
+    frame: Vec<u8>,
+    frame_pos: usize,
+    in_frame: bool,
+    inner_ended: bool,
+}
+
+impl<Inner: ReadLayered, D: Decoder> FramedReader<Inner, D> {
+    /// Construct a new `FramedReader` which decodes frames from `inner`
+    /// using `decoder`.
+    pub fn new(inner: Inner, decoder: D) -> Self {
+        Self {
+            inner,
+            decoder,
+            src: Vec::new(),
+            frame: Vec::new(),
+            frame_pos: 0,
+            in_frame: false,
+            inner_ended: false,
+        }
+    }
+
+    /// Gets a reference to the decoder.
+    pub fn decoder(&self) -> &D {
+        &self.decoder
+    }
+
+    /// Gets a mutable reference to the decoder.
+    pub fn decoder_mut(&mut self) -> &mut D {
+        &mut self.decoder
+    }
+
+    /// Consume this `FramedReader` and return the inner stream.
+    ///
+    /// Any buffered input which hasn't been decoded yet is discarded.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+
+    /// Decode the next frame, reading from the inner stream as needed.
+    /// Return `Some` status if no frame is available yet.
+    fn next_frame(&mut self) -> io::Result<Option<Status>> {
+        loop {
+            let frame = if self.inner_ended {
+                self.decoder.decode_eof(&mut self.src)?
+            } else {
+                self.decoder.decode(&mut self.src)?
+            };
+            if let Some(frame) = frame {
+                self.frame = frame;
+                self.frame_pos = 0;
+                self.in_frame = true;
+                return Ok(None);
+            }
+            if self.inner_ended {
+                return Ok(Some(Status::End));
+            }
+
+            let chunk =
+                default_suggested_buffer_size(&self.inner).max(self.inner.minimum_buffer_size());
+            let start = self.src.len();
+            self.src.resize(start + chunk, 0);
+            let result = self.inner.read_with_status(&mut self.src[start..]);
+            let (size, status) = match result {
+                Ok(size_and_status) => size_and_status,
+                Err(e) => {
+                    self.src.truncate(start);
+                    return Err(e);
+                }
+            };
+            self.src.truncate(start + size);
+            if status.is_end() {
+                self.inner_ended = true;
+            } else if size == 0 {
+                return Ok(Some(Status::active()));
+            }
+        }
+    }
+}
+
+impl<Inner: ReadLayered, D: Decoder> ReadLayered for FramedReader<Inner, D> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        if !self.in_frame {
+            if let Some(status) = self.next_frame()? {
+                return Ok((0, status));
+            }
+        }
+
+        let size = (self.frame.len() - self.frame_pos).min(buf.len());
+        buf[..size].copy_from_slice(&self.frame[self.frame_pos..self.frame_pos + size]);
+        self.frame_pos += size;
+        if self.frame_pos == self.frame.len() {
+            self.in_frame = false;
+            Ok((size, Status::push()))
+        } else {
+            Ok((size, Status::active()))
+        }
+    }
+}
+
+impl<Inner: ReadLayered, D> Bufferable for FramedReader<Inner, D> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon();
+        self.src.clear();
+        self.frame.clear();
+        self.in_frame = false;
+        self.inner_ended = true;
+    }
+}
+
+impl<Inner: ReadLayered, D: Decoder> Read for FramedReader<Inner, D> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug, D> fmt::Debug for FramedReader<Inner, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("FramedReader");
+        b.field("inner", &self.inner);
+        b.finish()
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{Bufferable, Encoder, WriteLayered};
+use std::fmt;
+use std::io::{self, Write};
+
+/// Adapts a [`WriteLayered`] to encode frames using an [`Encoder`].
+///
+/// Bytes written are accumulated into a frame, and a push, whether from
+/// `flush` or from `flush_with_status(Status::push())`, marks the end of the
+/// frame. The frame is then encoded and written to the inner stream, which is
+/// flushed. A push with no bytes written since the last frame doesn't produce
+/// an empty frame. On [`WriteLayered::close`], any pending frame is encoded
+/// before the inner stream is closed.
+pub struct FramedWriter<Inner, E> {
+    inner: Inner,
+    encoder: E,
+    frame: Vec<u8>,
+    encoded: Vec<u8>,
+}
+
+impl<Inner: WriteLayered, E: Encoder> FramedWriter<Inner, E> {
+    /// Construct a new `FramedWriter` which encodes frames to `inner` using
+    /// `encoder`.
+    pub fn new(inner: Inner, encoder: E) -> Self {
+        Self {
+            inner,
+            encoder,
+            frame: Vec::new(),
+            encoded: Vec::new(),
+        }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    fn write_frame(&mut self) -> io::Result<()> {
+        if self.frame.is_empty() {
+            return Ok(());
+        }
+        self.encoded.clear();
+        self.encoder.encode(&self.frame, &mut self.encoded)?;
+        self.frame.clear();
+        self.inner.write_all(&self.encoded)
+    }
+}
+
+impl<Inner: WriteLayered, E: Encoder> WriteLayered for FramedWriter<Inner, E> {
+    fn close(&mut self) -> io::Result<()> {
+        self.write_frame()?;
+        self.inner.close()
+    }
+}
+
+impl<Inner: WriteLayered, E> Bufferable for FramedWriter<Inner, E> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon();
+        self.frame.clear();
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: WriteLayered, E: Encoder> Write for FramedWriter<Inner, E> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.frame.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.write_frame()?;
+        self.inner.flush()
+    }
+}
+
+impl<Inner: fmt::Debug, E> fmt::Debug for FramedWriter<Inner, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("FramedWriter");
+        b.field("inner", &self.inner);
+        b.finish()
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    ReadLayered, Status,
+};
+use flate2::read::GzDecoder;
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Adapts a [`ReadLayered`] to decode a gzip stream read from it.
+///
+/// `Status::End` is reported at the end of the gzip stream. Pushes from the
+/// inner stream aren't forwarded, as the decoder doesn't preserve the
+/// correspondence between input and output positions.
+pub struct GzDecodeReader<Inner> {
+    decoder: GzDecoder<Inner>,
+}
+
+impl<Inner: ReadLayered> GzDecodeReader<Inner> {
+    /// Construct a new `GzDecodeReader` which wraps `inner`.
+    pub fn new(inner: Inner) -> Self {
+        Self {
+            decoder: GzDecoder::new(inner),
+        }
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &Inner {
+        self.decoder.get_ref()
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// It is inadvisable to directly read from the underlying reader.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        self.decoder.get_mut()
+    }
+}
+
+impl<Inner: ReadLayered> ReadLayered for GzDecodeReader<Inner> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        if buf.is_empty() {
+            return Ok((0, Status::active()));
+        }
+        match self.decoder.read(buf) {
+            Ok(0) => Ok((0, Status::End)),
+            Ok(size) => Ok((size, Status::active())),
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => Ok((0, Status::active())),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl<Inner: ReadLayered> Bufferable for GzDecodeReader<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.decoder.get_mut().abandon()
+    }
+}
+
+impl<Inner: ReadLayered> Read for GzDecodeReader<Inner> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for GzDecodeReader<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("GzDecodeReader");
+        b.field("inner", self.decoder.get_ref());
+        b.finish()
+    }
+}
@@ -0,0 +1,250 @@
+use crate::{Bufferable, WriteLayered};
+use flate2::{Compress, Compression, Crc, FlushCompress, Status as FlateStatus};
+use std::fmt;
+use std::io::{self, Write};
+
+/// A minimal gzip header: no file name, no modification time, unknown OS.
+const GZ_HEADER: [u8; 10] = [0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 0xff];
+
+/// Adapts a [`WriteLayered`] to gzip-compress the data written to it.
+///
+/// On [`WriteLayered::close`], the gzip stream is finished, writing the
+/// trailer, before the inner stream is closed. On [`Bufferable::abandon`],
+/// any pending compressed data is discarded and nothing further is written.
+pub struct GzEncodeWriter<Inner> {
+    inner: Inner,
+    compress: Compress,
+    crc: Crc,
+    out: Vec<u8>,
+    header_written: bool,
+    ended: bool,
+}
+
+impl<Inner: WriteLayered> GzEncodeWriter<Inner> {
+    /// Construct a new `GzEncodeWriter` which wraps `inner` with the default
+    /// compression level.
+    pub fn new(inner: Inner) -> Self {
+        Self::with_compression(inner, Compression::default())
+    }
+
+    /// Construct a new `GzEncodeWriter` which wraps `inner` with the given
+    /// compression level.
+    pub fn with_compression(inner: Inner, level: Compression) -> Self {
+        Self {
+            inner,
+            compress: Compress::new(level, false),
+            crc: Crc::new(),
+            out: Vec::new(),
+            header_written: false,
+            ended: false,
+        }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    fn check_open(&mut self) -> io::Result<()> {
+        if self.ended {
+            return Err(stream_already_ended());
+        }
+        if !self.header_written {
+            self.inner.write_all(&GZ_HEADER)?;
+            self.header_written = true;
+        }
+        Ok(())
+    }
+
+    /// Run the compressor over `input`, writing all output to the inner
+    /// stream, and return the number of bytes of `input` consumed and the
+    /// compressor status.
+    fn run(&mut self, input: &[u8], flush: FlushCompress) -> io::Result<(usize, FlateStatus)> {
+        let before = self.compress.total_in();
+        self.out.clear();
+        self.out.reserve(0x2000);
+        let status = self
+            .compress
+            .compress_vec(input, &mut self.out, flush)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        self.inner.write_all(&self.out)?;
+        Ok(((self.compress.total_in() - before) as usize, status))
+    }
+
+    /// Finish the gzip stream, writing the remaining compressed data and the
+    /// trailer.
+    fn finish(&mut self) -> io::Result<()> {
+        self.check_open()?;
+        self.ended = true;
+        self.drain(FlushCompress::Finish)?;
+        self.inner.write_all(&self.crc.sum().to_le_bytes())?;
+        self.inner.write_all(&self.crc.amount().to_le_bytes())
+    }
+
+    /// Run the compressor with `flush` until it produces no more output.
+    fn drain(&mut self, flush: FlushCompress) -> io::Result<()> {
+        loop {
+            let (_, status) = self.run(&[], flush)?;
+            if status == FlateStatus::StreamEnd || self.out.len() < self.out.capacity() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+impl<Inner: WriteLayered> WriteLayered for GzEncodeWriter<Inner> {
+    fn close(&mut self) -> io::Result<()> {
+        if self.ended {
+            return Err(stream_already_ended());
+        }
+        // If the gzip stream can't be finished, the inner stream would be
+        // left incomplete, so abandon it rather than leaving it open.
+        if let Err(e) = self.finish() {
+            self.abandon();
+            return Err(e);
+        }
+        self.inner.close()
+    }
+}
+
+impl<Inner: WriteLayered> Bufferable for GzEncodeWriter<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.ended = true;
+        self.out = Vec::new();
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: WriteLayered> Write for GzEncodeWriter<Inner> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.check_open()?;
+        let mut input = buf;
+        while !input.is_empty() {
+            let (consumed, _) = self.run(input, FlushCompress::None)?;
+            input = &input[consumed..];
+        }
+        self.crc.update(buf);
+        Ok(buf.len())
+    }
+
+    /// Perform a sync flush, so that a decoder can make progress on
+    /// everything written so far, and then flush the inner stream.
+    ///
+    /// A `flush_with_status(Status::push())` comes here by way of the
+    /// default [`WriteLayered::flush_with_status`], and so the inner stream
+    /// sees a plain `flush` rather than a push. That's intended, as the two
+    /// are defined to behave the same.
+    fn flush(&mut self) -> io::Result<()> {
+        self.check_open()?;
+        self.drain(FlushCompress::Sync)?;
+        self.inner.flush()
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for GzEncodeWriter<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("GzEncodeWriter");
+        b.field("inner", &self.inner);
+        b.finish()
+    }
+}
+
+fn stream_already_ended() -> io::Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, "stream has already ended")
+}
+
+#[test]
+fn test_gz_encode_writer_round_trip() {
+    use crate::{GzDecodeReader, ReadLayered, SliceReader, Status};
+    use std::io::Read;
+
+    let mut writer = GzEncodeWriter::new(io::Cursor::new(Vec::new()));
+    writer.write_all(b"hello ").unwrap();
+    writer.flush_with_status(Status::push()).unwrap();
+
+    // After a push, everything written so far is decodable, though the
+    // stream itself is incomplete.
+    let partial = writer.get_ref().get_ref().clone();
+    let mut reader = GzDecodeReader::new(SliceReader::new(&partial));
+    let mut buf = [0; 16];
+    let (size, status) = reader.read_with_status(&mut buf).unwrap();
+    assert_eq!((&buf[..size], status), (&b"hello "[..], Status::active()));
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap_err().kind(),
+        io::ErrorKind::UnexpectedEof
+    );
+
+    writer.write_all(b"world").unwrap();
+    writer.close().unwrap();
+
+    let mut reader = GzDecodeReader::new(SliceReader::new(writer.get_ref().get_ref()));
+    let mut decoded = String::new();
+    reader.read_to_string(&mut decoded).unwrap();
+    assert_eq!(decoded, "hello world");
+}
+
+#[test]
+fn test_gz_encode_writer_abandon() {
+    let mut vec = Vec::new();
+    let mut writer = GzEncodeWriter::new(io::Cursor::new(&mut vec));
+    writer.write_all(b"hello world").unwrap();
+    let len = writer.get_ref().get_ref().len();
+    writer.abandon();
+    assert!(writer.write_all(b"more").is_err());
+    drop(writer);
+    assert_eq!(vec.len(), len);
+}
+
+#[test]
+fn test_gz_encode_writer_close_error() {
+    /// A sink which accepts `limit` bytes and then fails.
+    struct Limited {
+        limit: usize,
+        abandoned: bool,
+    }
+    impl Write for Limited {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.limit == 0 {
+                return Err(io::Error::new(io::ErrorKind::WriteZero, "sink is full"));
+            }
+            let size = buf.len().min(self.limit);
+            self.limit -= size;
+            Ok(size)
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+    impl Bufferable for Limited {
+        fn abandon(&mut self) {
+            self.abandoned = true;
+        }
+    }
+    impl WriteLayered for Limited {
+        fn close(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut writer = GzEncodeWriter::new(Limited {
+        limit: GZ_HEADER.len(),
+        abandoned: false,
+    });
+    writer.write_all(b"hello world").unwrap();
+    assert_eq!(writer.close().unwrap_err().kind(), io::ErrorKind::WriteZero);
+    assert!(writer.get_ref().abandoned);
+    assert!(writer.write_all(b"more").is_err());
+}
@@ -0,0 +1,47 @@
+use std::io;
+use std::net::{Shutdown, TcpStream};
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+
+/// A duplex stream whose write direction can be shut down while its read
+/// direction stays open, so that the peer sees the end of the stream and
+/// can still send data back.
+///
+/// [`LayeredDuplexer::with_half_close`] uses this to implement
+/// [`WriteLayered::close`].
+///
+/// [`LayeredDuplexer::with_half_close`]: crate::LayeredDuplexer::with_half_close
+/// [`WriteLayered::close`]: crate::WriteLayered::close
+pub trait HalfClose {
+    /// Shut down the write direction of this stream.
+    fn shutdown_write(&self) -> io::Result<()>;
+}
+
+impl HalfClose for TcpStream {
+    #[inline]
+    fn shutdown_write(&self) -> io::Result<()> {
+        self.shutdown(Shutdown::Write)
+    }
+}
+
+#[cfg(unix)]
+impl HalfClose for UnixStream {
+    #[inline]
+    fn shutdown_write(&self) -> io::Result<()> {
+        self.shutdown(Shutdown::Write)
+    }
+}
+
+impl<T: HalfClose + ?Sized> HalfClose for &T {
+    #[inline]
+    fn shutdown_write(&self) -> io::Result<()> {
+        (**self).shutdown_write()
+    }
+}
+
+impl<T: HalfClose + ?Sized> HalfClose for &mut T {
+    #[inline]
+    fn shutdown_write(&self) -> io::Result<()> {
+        (**self).shutdown_write()
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored,
+    default_suggested_buffer_size, Bufferable, ReadLayered, Status,
+};
+use digest::{Digest, Output};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Adapts a [`ReadLayered`] to compute a digest of the data read from it,
+/// such as a SHA-256 hash with `sha2::Sha256`.
+///
+/// Data and statuses are passed through unchanged. Once the stream has been
+/// read, [`HashReader::finalize`] returns the digest.
+pub struct HashReader<Inner, D> {
+    inner: Inner,
+    digest: D,
+}
+
+impl<Inner: ReadLayered, D: Digest> HashReader<Inner, D> {
+    /// Construct a new `HashReader` which wraps `inner`.
+    pub fn new(inner: Inner) -> Self {
+        Self {
+            inner,
+            digest: D::new(),
+        }
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Bytes read directly from the underlying reader aren't hashed.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `HashReader` and return the digest of the data read.
+    pub fn finalize(self) -> Output<D> {
+        self.digest.finalize()
+    }
+}
+
+impl<Inner: ReadLayered, D: Digest> ReadLayered for HashReader<Inner, D> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        let (size, status) = self.inner.read_with_status(buf)?;
+        self.digest.update(&buf[..size]);
+        Ok((size, status))
+    }
+
+    #[inline]
+    fn minimum_buffer_size(&self) -> usize {
+        self.inner.minimum_buffer_size()
+    }
+}
+
+impl<Inner: ReadLayered, D> Bufferable for HashReader<Inner, D> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        match self.inner.suggested_buffer_size() {
+            0 => default_suggested_buffer_size(self),
+            size => size,
+        }
+    }
+}
+
+impl<Inner: ReadLayered, D: Digest> Read for HashReader<Inner, D> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug, D> fmt::Debug for HashReader<Inner, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("HashReader");
+        b.field("inner", &self.inner);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_hash_reader() {
+    use crate::{IntervalPushReader, SliceReader};
+    use sha2::Sha256;
+
+    let inner = IntervalPushReader::new(SliceReader::new(b"hello world"), 4);
+    let mut reader = HashReader::<_, Sha256>::new(inner);
+    let mut buf = [0; 16];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (4, Status::push())
+    );
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "o world");
+
+    let expected = [
+        0xb9, 0x4d, 0x27, 0xb9, 0x93, 0x4d, 0x3e, 0x08, 0xa5, 0x2e, 0x52, 0xd7, 0xda, 0x7d, 0xab,
+        0xfa, 0xc4, 0x84, 0xef, 0xe3, 0x7a, 0x53, 0x80, 0xee, 0x90, 0x88, 0xf7, 0xac, 0xe2, 0xef,
+        0xcd, 0xe9,
+    ];
+    assert_eq!(reader.finalize()[..], expected);
+}
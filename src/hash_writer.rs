@@ -0,0 +1,106 @@
+use crate::{Bufferable, WriteLayered};
+use digest::{Digest, Output};
+use std::fmt;
+use std::io::{self, Write};
+
+/// Adapts a [`WriteLayered`] to compute a digest of the data written to it,
+/// such as a SHA-256 hash with `sha2::Sha256`.
+///
+/// Data is passed through unchanged. Unlike [`Adler32Writer`], the digest
+/// isn't written to the stream; [`HashWriter::finalize`] returns it, and can
+/// be called after the stream is closed.
+///
+/// [`Adler32Writer`]: crate::Adler32Writer
+pub struct HashWriter<Inner, D> {
+    inner: Inner,
+    digest: D,
+}
+
+impl<Inner: WriteLayered, D: Digest> HashWriter<Inner, D> {
+    /// Construct a new `HashWriter` which wraps `inner`.
+    pub fn new(inner: Inner) -> Self {
+        Self {
+            inner,
+            digest: D::new(),
+        }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Bytes written directly to the underlying writer aren't hashed.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `HashWriter` and return the digest of the data written.
+    ///
+    /// This doesn't close the inner stream; to do so, call
+    /// [`WriteLayered::close`] first.
+    pub fn finalize(self) -> Output<D> {
+        self.digest.finalize()
+    }
+}
+
+impl<Inner: WriteLayered, D: Digest> WriteLayered for HashWriter<Inner, D> {
+    #[inline]
+    fn close(&mut self) -> io::Result<()> {
+        self.inner.close()
+    }
+}
+
+impl<Inner: WriteLayered, D> Bufferable for HashWriter<Inner, D> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: WriteLayered, D: Digest> Write for HashWriter<Inner, D> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let size = self.inner.write(buf)?;
+        self.digest.update(&buf[..size]);
+        Ok(size)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<Inner: fmt::Debug, D> fmt::Debug for HashWriter<Inner, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("HashWriter");
+        b.field("inner", &self.inner);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_hash_writer() {
+    use crate::{HashReader, ReadLayered, SliceReader, Status};
+    use sha2::Sha256;
+
+    let mut writer = HashWriter::<_, Sha256>::new(io::Cursor::new(Vec::new()));
+    writer.write_all(b"hello").unwrap();
+    writer.flush_with_status(Status::push()).unwrap();
+    writer.write_all(b" world").unwrap();
+    writer.close().unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"hello world");
+    let written = writer.finalize();
+
+    let mut reader = HashReader::<_, Sha256>::new(SliceReader::new(b"hello world"));
+    reader.read_to_end_with_status(&mut Vec::new()).unwrap();
+    assert_eq!(written, reader.finalize());
+}
@@ -0,0 +1,213 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    ReadLayered, Status,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Adapts a [`ReadLayered`] to discard every occurrence of a heartbeat byte
+/// sequence, such as the ones written by [`HeartbeatWriter`].
+///
+/// A heartbeat may straddle reads of the inner stream; bytes which could be
+/// the start of a heartbeat are held back until the next read decides it.
+/// [`HeartbeatWriter`] writes each heartbeat whole before flushing, so held
+/// bytes are returned as data when the inner stream pushes or ends.
+///
+/// [`HeartbeatWriter`]: crate::HeartbeatWriter
+pub struct HeartbeatFilterReader<Inner> {
+    inner: Inner,
+    heartbeat: Vec<u8>,
+    held: Vec<u8>,
+    scratch: Vec<u8>,
+    out: Vec<u8>,
+    out_pos: usize,
+    out_status: Status,
+}
+
+impl<Inner: ReadLayered> HeartbeatFilterReader<Inner> {
+    /// Construct a new `HeartbeatFilterReader` which wraps `inner` and
+    /// discards occurrences of `heartbeat`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `heartbeat` is empty.
+    pub fn new(inner: Inner, heartbeat: impl Into<Vec<u8>>) -> Self {
+        let heartbeat = heartbeat.into();
+        assert!(!heartbeat.is_empty(), "heartbeat must not be empty");
+        Self {
+            inner,
+            heartbeat,
+            held: Vec::new(),
+            scratch: Vec::new(),
+            out: Vec::new(),
+            out_pos: 0,
+            out_status: Status::active(),
+        }
+    }
+
+    /// Consume this `HeartbeatFilterReader` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+
+    /// Append `data` to the output, without the heartbeats in it. If
+    /// `may_continue` is set, a tail which could be the start of a
+    /// heartbeat is held back instead.
+    fn filter(&mut self, data: &[u8], may_continue: bool) {
+        let heartbeat = &self.heartbeat;
+        let mut i = 0;
+        while i < data.len() {
+            let rest = &data[i..];
+            if rest.starts_with(heartbeat) {
+                i += heartbeat.len();
+            } else if may_continue && heartbeat.starts_with(rest) {
+                self.held.extend_from_slice(rest);
+                return;
+            } else {
+                self.out.push(data[i]);
+                i += 1;
+            }
+        }
+    }
+}
+
+impl<Inner: ReadLayered> ReadLayered for HeartbeatFilterReader<Inner> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        while self.out_pos == self.out.len() {
+            self.out.clear();
+            self.out_pos = 0;
+
+            let len = buf.len().max(self.heartbeat.len());
+            self.scratch.resize(len, 0);
+            let (size, status) = match self.inner.read_with_status(&mut self.scratch) {
+                Ok(result) => result,
+                Err(e) => {
+                    self.scratch.clear();
+                    return Err(e);
+                }
+            };
+
+            let mut data = std::mem::take(&mut self.held);
+            data.extend_from_slice(&self.scratch[..size]);
+            self.filter(&data, status == Status::active());
+            self.out_status = status;
+
+            if self.out.is_empty() && status != Status::active() {
+                return Ok((0, status));
+            }
+        }
+
+        let size = (self.out.len() - self.out_pos).min(buf.len());
+        buf[..size].copy_from_slice(&self.out[self.out_pos..self.out_pos + size]);
+        self.out_pos += size;
+        if self.out_pos == self.out.len() {
+            Ok((size, self.out_status))
+        } else {
+            Ok((size, Status::active()))
+        }
+    }
+
+    #[inline]
+    fn minimum_buffer_size(&self) -> usize {
+        self.inner.minimum_buffer_size()
+    }
+}
+
+impl<Inner: ReadLayered> Bufferable for HeartbeatFilterReader<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.held = Vec::new();
+        self.scratch = Vec::new();
+        self.out = Vec::new();
+        self.out_pos = 0;
+        self.inner.abandon()
+    }
+}
+
+impl<Inner: ReadLayered> Read for HeartbeatFilterReader<Inner> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for HeartbeatFilterReader<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("HeartbeatFilterReader");
+        b.field("inner", &self.inner);
+        b.field("heartbeat", &self.heartbeat);
+        b.field("held", &self.held);
+        b.field("out", &(self.out.len() - self.out_pos));
+        b.finish()
+    }
+}
+
+#[test]
+fn test_heartbeat_round_trip() {
+    use crate::{HeartbeatWriter, SliceReader, WriteLayered};
+    use std::io::Write;
+
+    let mut writer = HeartbeatWriter::new(io::Cursor::new(Vec::new()), *b"<3");
+    writer.flush_with_status(Status::active()).unwrap();
+    writer.write_all(b"hello <").unwrap();
+    writer.flush_with_status(Status::active()).unwrap();
+    writer.flush_with_status(Status::active()).unwrap();
+    writer.write_all(b"world").unwrap();
+    writer.flush_with_status(Status::push()).unwrap();
+    writer.flush_with_status(Status::active()).unwrap();
+    writer.write_all(b"!<").unwrap();
+    writer.flush_with_status(Status::End).unwrap();
+    let written = writer.into_inner().into_inner();
+    assert_eq!(written, b"<3hello <<3world<3!<");
+
+    // Read with every buffer size, so that heartbeats straddle reads.
+    for len in 1..8 {
+        let mut reader = HeartbeatFilterReader::new(SliceReader::new(&written), *b"<3");
+        let mut buf = vec![0; len];
+        let mut payload = Vec::new();
+        loop {
+            let (size, status) = reader.read_with_status(&mut buf).unwrap();
+            payload.extend_from_slice(&buf[..size]);
+            if status.is_end() {
+                break;
+            }
+        }
+        assert_eq!(payload, b"hello <world!<", "buffer size {}", len);
+    }
+}
+
+#[test]
+fn test_heartbeat_filter_reader_push() {
+    use crate::PacketReader;
+
+    let packets = vec![b"ab<".to_vec(), b"<".to_vec(), b"3".to_vec()];
+    let mut reader = HeartbeatFilterReader::new(PacketReader::new(packets), *b"<3");
+    let mut buf = [0; 8];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (3, Status::push())
+    );
+    assert_eq!(&buf[..3], b"ab<");
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (1, Status::push())
+    );
+    assert_eq!(&buf[..1], b"<");
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (1, Status::End));
+    assert_eq!(&buf[..1], b"3");
+}
@@ -0,0 +1,125 @@
+use crate::{Activity, Bufferable, Status, WriteLayered};
+use std::fmt;
+use std::io::{self, Write};
+
+/// Adapts a [`WriteLayered`] to write a heartbeat byte sequence on idle
+/// flushes, to keep long-lived connections alive.
+///
+/// A `flush_with_status(Status::active())` with no data written since the
+/// previous flush writes the heartbeat to the inner stream. Other flushes are
+/// forwarded unchanged. The reading side can discard the heartbeats with
+/// [`HeartbeatFilterReader`].
+///
+/// [`HeartbeatFilterReader`]: crate::HeartbeatFilterReader
+pub struct HeartbeatWriter<Inner> {
+    inner: Inner,
+    heartbeat: Vec<u8>,
+    dirty: bool,
+}
+
+impl<Inner: WriteLayered> HeartbeatWriter<Inner> {
+    /// Construct a new `HeartbeatWriter` which wraps `inner` and writes
+    /// `heartbeat` on idle flushes.
+    pub fn new(inner: Inner, heartbeat: impl Into<Vec<u8>>) -> Self {
+        Self {
+            inner,
+            heartbeat: heartbeat.into(),
+            dirty: false,
+        }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `HeartbeatWriter` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+}
+
+impl<Inner: WriteLayered> WriteLayered for HeartbeatWriter<Inner> {
+    #[inline]
+    fn close(&mut self) -> io::Result<()> {
+        self.dirty = false;
+        self.inner.close()
+    }
+
+    fn flush_with_status(&mut self, status: Status) -> io::Result<()> {
+        if status == Status::Open(Activity::Active) && !self.dirty {
+            self.inner.write_all(&self.heartbeat)?;
+        }
+        self.dirty = false;
+        self.inner.flush_with_status(status)
+    }
+}
+
+impl<Inner: WriteLayered> Bufferable for HeartbeatWriter<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.dirty = false;
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: WriteLayered> Write for HeartbeatWriter<Inner> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let size = self.inner.write(buf)?;
+        if size != 0 {
+            self.dirty = true;
+        }
+        Ok(size)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.dirty = false;
+        self.inner.flush()
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for HeartbeatWriter<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("HeartbeatWriter");
+        b.field("inner", &self.inner);
+        b.field("heartbeat", &self.heartbeat);
+        b.field("dirty", &self.dirty);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_heartbeat_writer() {
+    let mut writer = HeartbeatWriter::new(io::Cursor::new(Vec::new()), *b"\0");
+
+    // Idle active flushes emit heartbeats.
+    writer.flush_with_status(Status::active()).unwrap();
+    writer.flush_with_status(Status::active()).unwrap();
+
+    // An active flush after data doesn't, but the next idle one does.
+    writer.write_all(b"abc").unwrap();
+    writer.flush_with_status(Status::active()).unwrap();
+    writer.flush_with_status(Status::active()).unwrap();
+
+    // Idle pushes and ends don't emit heartbeats.
+    writer.write_all(b"def").unwrap();
+    writer.flush_with_status(Status::push()).unwrap();
+    writer.flush_with_status(Status::push()).unwrap();
+    writer.flush_with_status(Status::End).unwrap();
+
+    assert_eq!(writer.get_ref().get_ref(), b"\0\0abc\0def");
+}
@@ -0,0 +1,116 @@
+use crate::{Bufferable, WriteLayered};
+use std::fmt;
+use std::io::{self, Write};
+
+/// Adapts a [`WriteLayered`] to prefix each line written to it with an
+/// indentation string.
+///
+/// The prefix is written when the first byte of a line is written, so a
+/// trailing newline doesn't leave a dangling prefix at the end of the output.
+pub struct IndentWriter<Inner> {
+    inner: Inner,
+    indent: Vec<u8>,
+    at_line_start: bool,
+}
+
+impl<Inner: WriteLayered> IndentWriter<Inner> {
+    /// Construct a new `IndentWriter` which wraps `inner` and prefixes each
+    /// line with `indent`.
+    pub fn new(inner: Inner, indent: impl Into<Vec<u8>>) -> Self {
+        Self {
+            inner,
+            indent: indent.into(),
+            at_line_start: true,
+        }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `IndentWriter` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+}
+
+impl<Inner: WriteLayered> WriteLayered for IndentWriter<Inner> {
+    #[inline]
+    fn close(&mut self) -> io::Result<()> {
+        self.inner.close()
+    }
+}
+
+impl<Inner: WriteLayered> Bufferable for IndentWriter<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: WriteLayered> Write for IndentWriter<Inner> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut rest = buf;
+        while !rest.is_empty() {
+            if self.at_line_start {
+                self.inner.write_all(&self.indent)?;
+                self.at_line_start = false;
+            }
+            let line_len = match rest.iter().position(|b| *b == b'\n') {
+                Some(newline) => {
+                    self.at_line_start = true;
+                    newline + 1
+                }
+                None => rest.len(),
+            };
+            self.inner.write_all(&rest[..line_len])?;
+            rest = &rest[line_len..];
+        }
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for IndentWriter<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("IndentWriter");
+        b.field("inner", &self.inner);
+        b.field("indent", &String::from_utf8_lossy(&self.indent));
+        b.finish()
+    }
+}
+
+#[test]
+fn test_indent_writer() {
+    let mut writer = IndentWriter::new(io::Cursor::new(Vec::new()), ">> ");
+    writer.write_all(b"a\nb").unwrap();
+    writer.close().unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b">> a\n>> b");
+}
+
+#[test]
+fn test_indent_writer_split_lines() {
+    let mut writer = IndentWriter::new(io::Cursor::new(Vec::new()), "  ");
+    writer.write_all(b"ab").unwrap();
+    writer.write_all(b"c\n").unwrap();
+    writer.write_all(b"d\ne\n").unwrap();
+    writer.close().unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"  abc\n  d\n  e\n");
+}
@@ -0,0 +1,167 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    ReadLayered, Status,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Adapts a [`ReadLayered`] to report a push every `interval` bytes.
+///
+/// Reads are shortened so that they end exactly at each `interval`-byte
+/// boundary, where the status is rewritten to `Status::push()`. Reads aren't
+/// shortened below the inner stream's `minimum_buffer_size`, so when that's
+/// larger than the distance to the next boundary, a read may extend past it,
+/// and the push is reported at the end of that read instead. A `Status::End`
+/// from the inner stream is always preserved.
+///
+/// This is mainly useful for testing how buffering layers handle pushes.
+pub struct IntervalPushReader<Inner> {
+    inner: Inner,
+    interval: u64,
+    until_push: u64,
+}
+
+impl<Inner: ReadLayered> IntervalPushReader<Inner> {
+    /// Construct a new `IntervalPushReader` which wraps `inner` and reports a
+    /// push every `interval` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    pub fn new(inner: Inner, interval: u64) -> Self {
+        assert_ne!(interval, 0, "push interval must be non-zero");
+        Self {
+            inner,
+            interval,
+            until_push: interval,
+        }
+    }
+
+    /// Consume this `IntervalPushReader` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+}
+
+impl<Inner: ReadLayered> ReadLayered for IntervalPushReader<Inner> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        let until_push = self.until_push.try_into().unwrap_or(usize::MAX);
+        let len = buf
+            .len()
+            .min(until_push.max(self.inner.minimum_buffer_size()));
+        let (size, status) = self.inner.read_with_status(&mut buf[..len])?;
+        if (size as u64) < self.until_push {
+            self.until_push -= size as u64;
+            return Ok((size, status));
+        }
+        // Keep later pushes on `interval`-byte boundaries even if this read
+        // went past one.
+        let past = (size as u64 - self.until_push) % self.interval;
+        self.until_push = self.interval - past;
+        if status.is_end() {
+            Ok((size, status))
+        } else {
+            Ok((size, Status::push()))
+        }
+    }
+
+    #[inline]
+    fn minimum_buffer_size(&self) -> usize {
+        self.inner.minimum_buffer_size()
+    }
+}
+
+impl<Inner: ReadLayered> Bufferable for IntervalPushReader<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon()
+    }
+}
+
+impl<Inner: ReadLayered> Read for IntervalPushReader<Inner> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for IntervalPushReader<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("IntervalPushReader");
+        b.field("inner", &self.inner);
+        b.field("interval", &self.interval);
+        b.field("until_push", &self.until_push);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_interval_push_reader() {
+    let mut reader = IntervalPushReader::new(crate::SliceReader::new(b"0123456789"), 4);
+    let mut buf = [0; 16];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (4, Status::push())
+    );
+    assert_eq!(&buf[..4], b"0123");
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (4, Status::push())
+    );
+    assert_eq!(&buf[..4], b"4567");
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (2, Status::End));
+    assert_eq!(&buf[..2], b"89");
+}
+
+#[test]
+fn test_interval_push_reader_minimum_buffer_size() {
+    use crate::SliceReader;
+
+    /// A reader which requires buffers of at least 6 bytes.
+    struct MinBuffer(SliceReader<'static>);
+    impl ReadLayered for MinBuffer {
+        fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+            assert!(buf.len() >= 6, "buffer is smaller than the minimum");
+            self.0.read_with_status(buf)
+        }
+        fn minimum_buffer_size(&self) -> usize {
+            6
+        }
+    }
+    impl Bufferable for MinBuffer {
+        fn abandon(&mut self) {
+            self.0.abandon()
+        }
+    }
+    impl Read for MinBuffer {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            default_read(self, buf)
+        }
+    }
+
+    let mut reader = IntervalPushReader::new(MinBuffer(SliceReader::new(b"0123456789")), 4);
+    let mut buf = [0; 16];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (6, Status::push())
+    );
+    assert_eq!(&buf[..6], b"012345");
+    assert_eq!(reader.until_push, 2);
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (4, Status::End));
+    assert_eq!(&buf[..4], b"6789");
+}
@@ -1,6 +1,7 @@
 use crate::{
     default_read, default_read_exact_using_status, default_read_to_end, default_read_to_string,
-    default_read_vectored, Bufferable, ReadLayered, Status, WriteLayered,
+    default_read_vectored, Bufferable, HalfClose, ReadHalf, ReadLayered, ReuniteError, Status,
+    WriteHalf, WriteLayered,
 };
 use duplex::Duplex;
 #[cfg(windows)]
@@ -10,6 +11,7 @@ use io_extras::os::windows::{
 };
 use std::fmt::{self, Arguments};
 use std::io::{self, IoSlice, IoSliceMut, Read, Write};
+use std::sync::Arc;
 #[cfg(feature = "terminal-io")]
 use terminal_io::DuplexTerminal;
 #[cfg(not(windows))]
@@ -20,11 +22,23 @@ use {
 
 /// Adapts an `Read` + `Write` to implement [`HalfDuplexLayered`].
 ///
+/// When constructed with [`LayeredDuplexer::with_half_close`], the read and
+/// write directions end independently: [`WriteLayered::close`] flushes and
+/// shuts down only the write direction, so the peer sees the end of the
+/// stream, and reads may continue until the inner stream reports end of
+/// stream. Otherwise, since `Read` + `Write` has no half-close operation,
+/// `close` drops the inner stream, ending both directions, so that the peer
+/// still sees the end of the stream. The inner stream is dropped once both
+/// directions have ended.
+///
 /// [`HalfDuplexLayered`]: crate::HalfDuplexLayered
 pub struct LayeredDuplexer<Inner> {
     inner: Option<Inner>,
+    shutdown_write: Option<fn(&Inner) -> io::Result<()>>,
     eos_as_push: bool,
     line_by_line: bool,
+    read_ended: bool,
+    write_closed: bool,
 }
 
 #[cfg(feature = "terminal-io")]
@@ -49,8 +63,11 @@ impl<Inner: Read + Write> LayeredDuplexer<Inner> {
     pub fn new(inner: Inner) -> Self {
         Self {
             inner: Some(inner),
+            shutdown_write: None,
             eos_as_push: false,
             line_by_line: false,
+            read_ended: false,
+            write_closed: false,
         }
     }
 
@@ -64,8 +81,11 @@ impl<Inner: Read + Write> LayeredDuplexer<Inner> {
     pub fn with_eos_as_push(inner: Inner) -> Self {
         Self {
             inner: Some(inner),
+            shutdown_write: None,
             eos_as_push: true,
             line_by_line: false,
+            read_ended: false,
+            write_closed: false,
         }
     }
 
@@ -74,8 +94,28 @@ impl<Inner: Read + Write> LayeredDuplexer<Inner> {
     pub fn line_by_line(inner: Inner) -> Self {
         Self {
             inner: Some(inner),
+            shutdown_write: None,
             eos_as_push: false,
             line_by_line: true,
+            read_ended: false,
+            write_closed: false,
+        }
+    }
+
+    /// Construct a new `LayeredDuplexer` which wraps `inner`, and closes its
+    /// write direction with [`HalfClose::shutdown_write`], so that reads can
+    /// continue after [`WriteLayered::close`].
+    pub fn with_half_close(inner: Inner) -> Self
+    where
+        Inner: HalfClose,
+    {
+        Self {
+            inner: Some(inner),
+            shutdown_write: Some(Inner::shutdown_write),
+            eos_as_push: false,
+            line_by_line: false,
+            read_ended: false,
+            write_closed: false,
         }
     }
 
@@ -95,12 +135,99 @@ impl<Inner: Read + Write> LayeredDuplexer<Inner> {
     pub fn abandon_into_inner(mut self) -> Option<Inner> {
         self.inner.take()
     }
+    /// Mark the read direction as ended, and drop the inner stream if the
+    /// write direction has also ended.
+    fn end_read(&mut self) {
+        self.read_ended = true;
+        if self.write_closed {
+            drop(self.inner.take().unwrap());
+        }
+    }
+}
+
+impl<Inner> LayeredDuplexer<Inner>
+where
+    for<'a> &'a Inner: Read + Write,
+{
+    /// Split this `LayeredDuplexer` into independent read and write halves,
+    /// which can be used from different threads.
+    ///
+    /// This requires reading and writing through shared references to the
+    /// inner stream, as with `TcpStream` and `UnixStream`. Each half starts
+    /// in the state of its direction here; for example, if the write
+    /// direction has been closed, the write half is closed. Each half may be
+    /// abandoned independently, and the inner stream is dropped once both
+    /// halves have been dropped. The write half panics on drop if it wasn't
+    /// closed or abandoned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stream has been abandoned, or both directions have
+    /// ended.
+    pub fn split(mut self) -> (ReadHalf<Inner>, WriteHalf<Inner>) {
+        let inner = Arc::new(
+            self.inner
+                .take()
+                .expect("split() called on closed LayeredDuplexer"),
+        );
+        let read = ReadHalf {
+            inner: Arc::clone(&inner),
+            eos_as_push: self.eos_as_push,
+            line_by_line: self.line_by_line,
+            ended: self.read_ended,
+        };
+        let write = WriteHalf {
+            inner,
+            shutdown_write: self.shutdown_write,
+            closed: self.write_closed,
+        };
+        (read, write)
+    }
+
+    /// Recombine halves returned by [`LayeredDuplexer::split`].
+    ///
+    /// Fails, returning the halves, if they aren't from the same stream.
+    pub fn reunite(
+        read: ReadHalf<Inner>,
+        mut write: WriteHalf<Inner>,
+    ) -> Result<Self, ReuniteError<Inner>> {
+        if !Arc::ptr_eq(&read.inner, &write.inner) {
+            return Err(ReuniteError(read, write));
+        }
+        let inner = Arc::clone(&read.inner);
+        let eos_as_push = read.eos_as_push;
+        let line_by_line = read.line_by_line;
+        let read_ended = read.ended;
+        let write_closed = write.closed;
+        let shutdown_write = write.shutdown_write;
+        drop(read);
+        // Suppress the write half's drop assertion; the write direction's
+        // state carries over to the new `LayeredDuplexer`.
+        write.closed = true;
+        drop(write);
+        let inner = match Arc::try_unwrap(inner) {
+            Ok(inner) => inner,
+            Err(_) => unreachable!("the halves hold the only references"),
+        };
+        Ok(Self {
+            inner: if read_ended && write_closed {
+                None
+            } else {
+                Some(inner)
+            },
+            shutdown_write,
+            eos_as_push,
+            line_by_line,
+            read_ended,
+            write_closed,
+        })
+    }
 }
 
 impl<Inner: Read + Write> ReadLayered for LayeredDuplexer<Inner> {
     #[inline]
     fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
-        if self.inner.is_none() {
+        if self.inner.is_none() || self.read_ended {
             return Ok((0, Status::End));
         }
         match self.inner.as_mut().unwrap().read(buf) {
@@ -108,7 +235,7 @@ impl<Inner: Read + Write> ReadLayered for LayeredDuplexer<Inner> {
                 if self.eos_as_push {
                     Ok((0, Status::push()))
                 } else {
-                    drop(self.inner.take().unwrap());
+                    self.end_read();
                     Ok((0, Status::End))
                 }
             }
@@ -132,7 +259,7 @@ impl<Inner: Read + Write> ReadLayered for LayeredDuplexer<Inner> {
         &mut self,
         bufs: &mut [IoSliceMut<'_>],
     ) -> io::Result<(usize, Status)> {
-        if self.inner.is_none() {
+        if self.inner.is_none() || self.read_ended {
             return Ok((0, Status::End));
         }
         match self.inner.as_mut().unwrap().read_vectored(bufs) {
@@ -140,7 +267,7 @@ impl<Inner: Read + Write> ReadLayered for LayeredDuplexer<Inner> {
                 if self.eos_as_push {
                     Ok((0, Status::push()))
                 } else {
-                    drop(self.inner.take().unwrap());
+                    self.end_read();
                     Ok((0, Status::End))
                 }
             }
@@ -149,11 +276,11 @@ impl<Inner: Read + Write> ReadLayered for LayeredDuplexer<Inner> {
                     let mut i = size;
                     let mut saw_line = false;
                     for buf in bufs.iter() {
-                        if i < buf.len() {
-                            saw_line = buf[i - 1] == b'\n';
+                        if i <= buf.len() {
+                            saw_line = i != 0 && buf[i - 1] == b'\n';
                             break;
                         }
-                        i -= bufs.len();
+                        i -= buf.len();
                     }
                     if saw_line {
                         return Ok((size, Status::push()));
@@ -225,11 +352,26 @@ impl<Inner: Read + Write> Read for LayeredDuplexer<Inner> {
 }
 
 impl<Inner: Read + Write> WriteLayered for LayeredDuplexer<Inner> {
+    /// Flush and close the write direction. With a half-close, this leaves
+    /// the read direction open; otherwise it drops the inner stream.
+    ///
+    /// The default [`WriteLayered::flush_with_status`] comes here on
+    /// `Status::End`, so that behaves the same way.
     #[inline]
     fn close(&mut self) -> io::Result<()> {
         match &mut self.inner {
-            Some(_) => self.inner.take().unwrap().flush(),
-            None => Err(stream_already_ended()),
+            Some(inner) if !self.write_closed => {
+                self.write_closed = true;
+                let result = match self.shutdown_write {
+                    Some(shutdown_write) => inner.flush().and_then(|()| shutdown_write(inner)),
+                    None => inner.flush(),
+                };
+                if self.read_ended || self.shutdown_write.is_none() || result.is_err() {
+                    drop(self.inner.take().unwrap());
+                }
+                result
+            }
+            _ => Err(stream_already_ended()),
         }
     }
 }
@@ -238,33 +380,33 @@ impl<Inner: Read + Write> Write for LayeredDuplexer<Inner> {
     #[inline]
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         match &mut self.inner {
-            Some(inner) => inner.write(buf).map_err(|e| {
+            Some(inner) if !self.write_closed => inner.write(buf).map_err(|e| {
                 drop(self.inner.take().unwrap());
                 e
             }),
-            None => Err(stream_already_ended()),
+            _ => Err(stream_already_ended()),
         }
     }
 
     #[inline]
     fn flush(&mut self) -> io::Result<()> {
         match &mut self.inner {
-            Some(inner) => inner.flush().map_err(|e| {
+            Some(inner) if !self.write_closed => inner.flush().map_err(|e| {
                 drop(self.inner.take().unwrap());
                 e
             }),
-            None => Err(stream_already_ended()),
+            _ => Err(stream_already_ended()),
         }
     }
 
     #[inline]
     fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
         match &mut self.inner {
-            Some(inner) => inner.write_vectored(bufs).map_err(|e| {
+            Some(inner) if !self.write_closed => inner.write_vectored(bufs).map_err(|e| {
                 drop(self.inner.take().unwrap());
                 e
             }),
-            None => Err(stream_already_ended()),
+            _ => Err(stream_already_ended()),
         }
     }
 
@@ -272,19 +414,19 @@ impl<Inner: Read + Write> Write for LayeredDuplexer<Inner> {
     #[inline]
     fn is_write_vectored(&self) -> bool {
         match &self.inner {
-            Some(inner) => inner.is_write_vectored(),
-            None => false,
+            Some(inner) if !self.write_closed => inner.is_write_vectored(),
+            _ => false,
         }
     }
 
     #[inline]
     fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
         match &mut self.inner {
-            Some(inner) => inner.write_all(buf).map_err(|e| {
+            Some(inner) if !self.write_closed => inner.write_all(buf).map_err(|e| {
                 drop(self.inner.take().unwrap());
                 e
             }),
-            None => Err(stream_already_ended()),
+            _ => Err(stream_already_ended()),
         }
     }
 
@@ -292,22 +434,22 @@ impl<Inner: Read + Write> Write for LayeredDuplexer<Inner> {
     #[inline]
     fn write_all_vectored(&mut self, bufs: &mut [IoSlice<'_>]) -> io::Result<()> {
         match &mut self.inner {
-            Some(inner) => inner.write_all_vectored(bufs).map_err(|e| {
+            Some(inner) if !self.write_closed => inner.write_all_vectored(bufs).map_err(|e| {
                 drop(self.inner.take().unwrap());
                 e
             }),
-            None => Err(stream_already_ended()),
+            _ => Err(stream_already_ended()),
         }
     }
 
     #[inline]
     fn write_fmt(&mut self, fmt: Arguments<'_>) -> io::Result<()> {
         match &mut self.inner {
-            Some(inner) => inner.write_fmt(fmt).map_err(|e| {
+            Some(inner) if !self.write_closed => inner.write_fmt(fmt).map_err(|e| {
                 drop(self.inner.take().unwrap());
                 e
             }),
-            None => Err(stream_already_ended()),
+            _ => Err(stream_already_ended()),
         }
     }
 }
@@ -405,6 +547,31 @@ impl<Inner: Duplex + AsReadWriteFd> AsReadWriteFd for LayeredDuplexer<Inner> {
     }
 }
 
+#[cfg(not(windows))]
+impl<Inner: Duplex + AsReadWriteFd> LayeredDuplexer<Inner> {
+    /// Like [`AsReadWriteFd::as_read_fd`], but returns `None` instead of
+    /// panicking if the read direction has ended or the stream has been
+    /// abandoned.
+    #[inline]
+    pub fn try_as_read_fd(&self) -> Option<BorrowedFd<'_>> {
+        match &self.inner {
+            Some(inner) if !self.read_ended => Some(inner.as_read_fd()),
+            _ => None,
+        }
+    }
+
+    /// Like [`AsReadWriteFd::as_write_fd`], but returns `None` instead of
+    /// panicking if the write direction has been closed or the stream has
+    /// been abandoned.
+    #[inline]
+    pub fn try_as_write_fd(&self) -> Option<BorrowedFd<'_>> {
+        match &self.inner {
+            Some(inner) if !self.write_closed => Some(inner.as_write_fd()),
+            _ => None,
+        }
+    }
+}
+
 #[cfg(windows)]
 impl<Inner: Duplex + AsRawReadWriteHandleOrSocket> AsRawReadWriteHandleOrSocket
     for LayeredDuplexer<Inner>
@@ -447,6 +614,31 @@ impl<Inner: Duplex + AsReadWriteHandleOrSocket> AsReadWriteHandleOrSocket
     }
 }
 
+#[cfg(windows)]
+impl<Inner: Duplex + AsReadWriteHandleOrSocket> LayeredDuplexer<Inner> {
+    /// Like [`AsReadWriteHandleOrSocket::as_read_handle_or_socket`], but
+    /// returns `None` instead of panicking if the read direction has ended or
+    /// the stream has been abandoned.
+    #[inline]
+    pub fn try_as_read_handle_or_socket(&self) -> Option<BorrowedHandleOrSocket<'_>> {
+        match &self.inner {
+            Some(inner) if !self.read_ended => Some(inner.as_read_handle_or_socket()),
+            _ => None,
+        }
+    }
+
+    /// Like [`AsReadWriteHandleOrSocket::as_write_handle_or_socket`], but
+    /// returns `None` instead of panicking if the write direction has been
+    /// closed or the stream has been abandoned.
+    #[inline]
+    pub fn try_as_write_handle_or_socket(&self) -> Option<BorrowedHandleOrSocket<'_>> {
+        match &self.inner {
+            Some(inner) if !self.write_closed => Some(inner.as_write_handle_or_socket()),
+            _ => None,
+        }
+    }
+}
+
 impl<Inner: fmt::Debug> fmt::Debug for LayeredDuplexer<Inner> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut b = f.debug_struct("LayeredDuplexer");
@@ -461,7 +653,10 @@ fn stream_already_ended() -> io::Error {
 
 impl<Inner> Drop for LayeredDuplexer<Inner> {
     fn drop(&mut self) {
-        assert!(self.inner.is_none(), "stream was not closed or abandoned");
+        assert!(
+            self.inner.is_none() || self.read_ended || self.write_closed,
+            "stream was not closed or abandoned"
+        );
     }
 }
 
@@ -473,3 +668,159 @@ fn test_layered_duplexion() {
     reader.read_to_string(&mut s).unwrap();
     assert_eq!(s, "hello world");
 }
+
+#[cfg(unix)]
+#[test]
+fn test_layered_duplexer_half_close() {
+    use std::os::unix::net::UnixStream;
+
+    let (local, mut peer) = UnixStream::pair().unwrap();
+    let mut duplexer = LayeredDuplexer::with_half_close(local);
+    duplexer.write_all(b"ping").unwrap();
+    duplexer.close().unwrap();
+    assert_eq!(
+        duplexer.write_all(b"more").unwrap_err().kind(),
+        io::ErrorKind::BrokenPipe
+    );
+
+    // The peer sees the end of the stream, and can still respond.
+    let mut request = Vec::new();
+    peer.read_to_end(&mut request).unwrap();
+    assert_eq!(request, b"ping");
+    peer.write_all(b"pong").unwrap();
+    drop(peer);
+
+    let mut s = String::new();
+    duplexer.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "pong");
+    assert!(duplexer.inner.is_none());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_layered_duplexer_close_without_half_close() {
+    use std::os::unix::net::UnixStream;
+
+    // Without a half-close, closing drops the inner stream, so the peer
+    // still sees the end of the stream.
+    let (local, mut peer) = UnixStream::pair().unwrap();
+    let mut duplexer = LayeredDuplexer::new(local);
+    duplexer.write_all(b"ping").unwrap();
+    duplexer.close().unwrap();
+    assert!(duplexer.inner.is_none());
+
+    let mut request = Vec::new();
+    peer.read_to_end(&mut request).unwrap();
+    assert_eq!(request, b"ping");
+    let mut buf = [0; 4];
+    assert_eq!(
+        duplexer.read_with_status(&mut buf).unwrap(),
+        (0, Status::End)
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_layered_duplexer_flush_end() {
+    use std::os::unix::net::UnixStream;
+
+    let (local, mut peer) = UnixStream::pair().unwrap();
+    let mut duplexer = LayeredDuplexer::with_half_close(local);
+    duplexer.write_all(b"request").unwrap();
+    duplexer.flush_with_status(Status::End).unwrap();
+    assert!(duplexer.write_all(b"more").is_err());
+
+    let mut request = Vec::new();
+    peer.read_to_end(&mut request).unwrap();
+    assert_eq!(request, b"request");
+    peer.write_all(b"response").unwrap();
+    drop(peer);
+    let mut s = String::new();
+    duplexer.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "response");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_layered_duplexer_try_as_fd() {
+    use std::os::unix::net::UnixStream;
+
+    let (local, _peer) = UnixStream::pair().unwrap();
+    let mut duplexer = LayeredDuplexer::with_half_close(local);
+    assert!(duplexer.try_as_read_fd().is_some());
+    assert!(duplexer.try_as_write_fd().is_some());
+    duplexer.close().unwrap();
+    assert!(duplexer.try_as_read_fd().is_some());
+    assert!(duplexer.try_as_write_fd().is_none());
+    duplexer.abandon();
+    assert!(duplexer.try_as_read_fd().is_none());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_layered_duplexer_line_by_line_vectored() {
+    use std::os::unix::net::UnixStream;
+
+    let (local, mut peer) = UnixStream::pair().unwrap();
+    let mut duplexer = LayeredDuplexer::line_by_line(local);
+    peer.write_all(b"abcd\n").unwrap();
+    let mut a = [0; 3];
+    let mut b = [0; 8];
+    let mut bufs = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)];
+    assert_eq!(
+        duplexer.read_vectored_with_status(&mut bufs).unwrap(),
+        (5, Status::push())
+    );
+    duplexer.abandon();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_layered_duplexer_split() {
+    use std::net::Shutdown;
+    use std::os::unix::net::UnixStream;
+    use std::thread;
+
+    let (local, mut peer) = UnixStream::pair().unwrap();
+    let duplexer = LayeredDuplexer::new(local);
+    let (mut read, mut write) = duplexer.split();
+
+    let reader = thread::spawn(move || {
+        let mut s = String::new();
+        read.read_to_string(&mut s).unwrap();
+        (read, s)
+    });
+
+    write.write_all(b"ping").unwrap();
+    write.flush_with_status(Status::push()).unwrap();
+    let mut buf = [0; 4];
+    peer.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"ping");
+    peer.write_all(b"pong").unwrap();
+    peer.shutdown(Shutdown::Write).unwrap();
+
+    let (read, s) = reader.join().unwrap();
+    assert_eq!(s, "pong");
+
+    // The write direction is still open after reuniting.
+    let mut duplexer = LayeredDuplexer::reunite(read, write).unwrap();
+    duplexer.write_all(b"!").unwrap();
+    duplexer.close().unwrap();
+    let mut rest = Vec::new();
+    peer.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, b"!");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_layered_duplexer_reunite_mismatch() {
+    use std::os::unix::net::UnixStream;
+
+    let (a, _a_peer) = UnixStream::pair().unwrap();
+    let (b, _b_peer) = UnixStream::pair().unwrap();
+    let (read_a, mut write_a) = LayeredDuplexer::new(a).split();
+    let (_read_b, write_b) = LayeredDuplexer::new(b).split();
+    let ReuniteError(_read_a, mut write_b) = LayeredDuplexer::reunite(read_a, write_b).unwrap_err();
+    write_a.abandon();
+    write_b.abandon();
+}
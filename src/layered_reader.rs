@@ -75,6 +75,43 @@ impl<Inner: Read> LayeredReader<Inner> {
         }
     }
 
+    /// Gets a reference to the underlying reader.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stream has ended or been abandoned.
+    pub fn get_ref(&self) -> &Inner {
+        self.inner
+            .as_ref()
+            .expect("get_ref() called on closed LayeredReader")
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Reading directly from the underlying reader bypasses this
+    /// `LayeredReader`'s status tracking; for example, an end of stream
+    /// found that way isn't reported as `Status::End`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stream has ended or been abandoned.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        self.inner
+            .as_mut()
+            .expect("get_mut() called on closed LayeredReader")
+    }
+
+    /// Close this `LayeredReader` and return the inner stream.
+    ///
+    /// Reads don't need flushing, so this just returns the inner stream, but
+    /// it fails if the stream has already ended or been abandoned, like
+    /// [`LayeredWriter::close_into_inner`].
+    ///
+    /// [`LayeredWriter::close_into_inner`]: crate::LayeredWriter::close_into_inner
+    pub fn close_into_inner(self) -> io::Result<Inner> {
+        self.inner.ok_or_else(stream_already_ended)
+    }
+
     /// Consume this `LayeredReader` and return the inner stream.
     pub fn abandon_into_inner(self) -> Option<Inner> {
         self.inner
@@ -133,11 +170,11 @@ impl<Inner: Read> ReadLayered for LayeredReader<Inner> {
                     let mut i = size;
                     let mut saw_line = false;
                     for buf in bufs.iter() {
-                        if i < buf.len() {
-                            saw_line = buf[i - 1] == b'\n';
+                        if i <= buf.len() {
+                            saw_line = i != 0 && buf[i - 1] == b'\n';
                             break;
                         }
-                        i -= bufs.len();
+                        i -= buf.len();
                     }
                     if saw_line {
                         return Ok((size, Status::push()));
@@ -166,7 +203,7 @@ impl<Inner: Read> Read for LayeredReader<Inner> {
     #[inline]
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         default_read(self, buf).map_err(|e| {
-            drop(self.inner.take().unwrap());
+            self.abandon();
             e
         })
     }
@@ -174,7 +211,7 @@ impl<Inner: Read> Read for LayeredReader<Inner> {
     #[inline]
     fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         default_read_vectored(self, bufs).map_err(|e| {
-            drop(self.inner.take().unwrap());
+            self.abandon();
             e
         })
     }
@@ -191,7 +228,7 @@ impl<Inner: Read> Read for LayeredReader<Inner> {
     #[inline]
     fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
         default_read_to_end(self, buf).map_err(|e| {
-            drop(self.inner.take().unwrap());
+            self.abandon();
             e
         })
     }
@@ -199,7 +236,7 @@ impl<Inner: Read> Read for LayeredReader<Inner> {
     #[inline]
     fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
         default_read_to_string(self, buf).map_err(|e| {
-            drop(self.inner.take().unwrap());
+            self.abandon();
             e
         })
     }
@@ -209,7 +246,7 @@ impl<Inner: Read> Read for LayeredReader<Inner> {
         default_read_exact_using_status(self, buf)
             .map(|_status| ())
             .map_err(|e| {
-                drop(self.inner.take().unwrap());
+                self.abandon();
                 e
             })
     }
@@ -259,6 +296,16 @@ impl<Inner: Read + AsFd> AsFd for LayeredReader<Inner> {
     }
 }
 
+#[cfg(not(windows))]
+impl<Inner: Read + AsFd> LayeredReader<Inner> {
+    /// Like [`AsFd::as_fd`], but returns `None` instead of panicking if the
+    /// stream has been closed or abandoned.
+    #[inline]
+    pub fn try_as_fd(&self) -> Option<BorrowedFd<'_>> {
+        self.inner.as_ref().map(AsFd::as_fd)
+    }
+}
+
 #[cfg(windows)]
 impl<Inner: Read + AsRawHandleOrSocket> AsRawHandleOrSocket for LayeredReader<Inner> {
     #[inline]
@@ -281,6 +328,18 @@ impl<Inner: Read + AsHandleOrSocket> AsHandleOrSocket for LayeredReader<Inner> {
     }
 }
 
+#[cfg(windows)]
+impl<Inner: Read + AsHandleOrSocket> LayeredReader<Inner> {
+    /// Like [`AsHandleOrSocket::as_handle_or_socket`], but returns `None`
+    /// instead of panicking if the stream has been closed or abandoned.
+    #[inline]
+    pub fn try_as_handle_or_socket(&self) -> Option<BorrowedHandleOrSocket<'_>> {
+        self.inner
+            .as_ref()
+            .map(AsHandleOrSocket::as_handle_or_socket)
+    }
+}
+
 impl<Inner: fmt::Debug> fmt::Debug for LayeredReader<Inner> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut b = f.debug_struct("LayeredReader");
@@ -289,6 +348,10 @@ impl<Inner: fmt::Debug> fmt::Debug for LayeredReader<Inner> {
     }
 }
 
+fn stream_already_ended() -> io::Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, "stream has already ended")
+}
+
 #[test]
 fn test_layered_reader() {
     let mut input = io::Cursor::new(b"hello world");
@@ -297,3 +360,83 @@ fn test_layered_reader() {
     reader.read_to_string(&mut s).unwrap();
     assert_eq!(s, "hello world");
 }
+
+#[cfg(not(windows))]
+#[test]
+fn test_layered_reader_try_as_fd() {
+    let file = std::fs::File::open("Cargo.toml").unwrap();
+    let mut reader = LayeredReader::new(file);
+    assert!(reader.try_as_fd().is_some());
+    reader.abandon();
+    assert!(reader.try_as_fd().is_none());
+}
+
+#[test]
+fn test_layered_reader_get_ref() {
+    use std::io::{Seek, SeekFrom};
+
+    let mut reader = LayeredReader::new(io::Cursor::new(b"hello world"));
+    let mut buf = [0; 5];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(reader.get_ref().position(), 5);
+    reader.get_mut().seek(SeekFrom::Start(6)).unwrap();
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "world");
+}
+
+#[test]
+fn test_layered_reader_line_by_line_vectored() {
+    // The newline is in the second buffer, and the data ends partway
+    // through it.
+    let mut reader = LayeredReader::line_by_line(io::Cursor::new(b"abcd\n"));
+    let mut a = [0; 3];
+    let mut b = [0; 8];
+    let mut bufs = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)];
+    assert_eq!(
+        reader.read_vectored_with_status(&mut bufs).unwrap(),
+        (5, Status::push())
+    );
+
+    // The newline is the last byte of the first buffer.
+    let mut reader = LayeredReader::line_by_line(io::Cursor::new(b"ab\n"));
+    let mut a = [0; 3];
+    let mut b = [0; 8];
+    let mut bufs = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)];
+    assert_eq!(
+        reader.read_vectored_with_status(&mut bufs).unwrap(),
+        (3, Status::push())
+    );
+
+    // No newline at the end of the data.
+    let mut reader = LayeredReader::line_by_line(io::Cursor::new(b"ab\ncde"));
+    let mut a = [0; 2];
+    let mut b = [0; 2];
+    let mut c = [0; 8];
+    let mut bufs = [
+        IoSliceMut::new(&mut a),
+        IoSliceMut::new(&mut b),
+        IoSliceMut::new(&mut c),
+    ];
+    assert_eq!(
+        reader.read_vectored_with_status(&mut bufs).unwrap(),
+        (6, Status::active())
+    );
+}
+
+#[test]
+fn test_layered_reader_close_into_inner() {
+    let mut reader = LayeredReader::new(io::Cursor::new(b"hello"));
+    let mut buf = [0; 2];
+    reader.read_exact(&mut buf).unwrap();
+    let inner = reader.close_into_inner().unwrap();
+    assert_eq!(inner.position(), 2);
+
+    let mut reader = LayeredReader::new(io::Cursor::new(b"hello"));
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(
+        reader.close_into_inner().unwrap_err().kind(),
+        io::ErrorKind::BrokenPipe
+    );
+}
@@ -1,4 +1,4 @@
-use crate::{Bufferable, WriteLayered};
+use crate::{Activity, Bufferable, Status, WriteLayered};
 #[cfg(windows)]
 use io_extras::os::windows::{
     AsHandleOrSocket, AsRawHandleOrSocket, BorrowedHandleOrSocket, RawHandleOrSocket,
@@ -14,12 +14,46 @@ use {
 /// Adapts a [`std::io::Write`] to implement [`WriteLayered`].
 pub struct LayeredWriter<Inner> {
     inner: Option<Inner>,
+    line_by_line: bool,
+}
+
+#[cfg(feature = "terminal-io")]
+impl<Inner: terminal_io::WriteTerminal> LayeredWriter<Inner> {
+    /// Construct a new `LayeredWriter` which wraps `inner`, which implements
+    /// `WriteTerminal`, and automatically sets the `line_by_line` setting if
+    /// `inner` is a terminal.
+    pub fn maybe_terminal(inner: Inner) -> Self {
+        let line_by_line = inner.is_output_terminal();
+
+        if line_by_line {
+            Self::line_by_line(inner)
+        } else {
+            Self::new(inner)
+        }
+    }
 }
 
 impl<Inner: Write> LayeredWriter<Inner> {
     /// Construct a new `LayeredWriter` which wraps `inner`.
     pub fn new(inner: Inner) -> Self {
-        Self { inner: Some(inner) }
+        Self {
+            inner: Some(inner),
+            line_by_line: false,
+        }
+    }
+
+    /// Construct a new `LayeredWriter` which wraps an `inner` which displays
+    /// its output as it arrives, such as stdout on a terminal.
+    ///
+    /// `flush_with_status(Status::active())` normally does nothing, leaving
+    /// it to the inner stream to decide when to transmit its data. With this
+    /// setting, it flushes the inner stream, as a push does, so that output
+    /// is displayed at each active flush, like a line-buffered stream.
+    pub fn line_by_line(inner: Inner) -> Self {
+        Self {
+            inner: Some(inner),
+            line_by_line: true,
+        }
     }
 
     /// Gets a reference to the underlying writer.
@@ -64,6 +98,15 @@ impl<Inner: Write> WriteLayered for LayeredWriter<Inner> {
             None => Err(stream_already_ended()),
         }
     }
+
+    #[inline]
+    fn flush_with_status(&mut self, status: Status) -> io::Result<()> {
+        match status {
+            Status::Open(Activity::Active) if !self.line_by_line => Ok(()),
+            Status::Open(_) => self.flush(),
+            Status::End => self.close(),
+        }
+    }
 }
 
 impl<Inner> Bufferable for LayeredWriter<Inner> {
@@ -197,6 +240,16 @@ impl<Inner: Write + AsFd> AsFd for LayeredWriter<Inner> {
     }
 }
 
+#[cfg(not(windows))]
+impl<Inner: Write + AsFd> LayeredWriter<Inner> {
+    /// Like [`AsFd::as_fd`], but returns `None` instead of panicking if the
+    /// stream has been closed or abandoned.
+    #[inline]
+    pub fn try_as_fd(&self) -> Option<BorrowedFd<'_>> {
+        self.inner.as_ref().map(AsFd::as_fd)
+    }
+}
+
 #[cfg(windows)]
 impl<Inner: Write + AsRawHandleOrSocket> AsRawHandleOrSocket for LayeredWriter<Inner> {
     #[inline]
@@ -219,10 +272,23 @@ impl<Inner: Write + AsHandleOrSocket> AsHandleOrSocket for LayeredWriter<Inner>
     }
 }
 
+#[cfg(windows)]
+impl<Inner: Write + AsHandleOrSocket> LayeredWriter<Inner> {
+    /// Like [`AsHandleOrSocket::as_handle_or_socket`], but returns `None`
+    /// instead of panicking if the stream has been closed or abandoned.
+    #[inline]
+    pub fn try_as_handle_or_socket(&self) -> Option<BorrowedHandleOrSocket<'_>> {
+        self.inner
+            .as_ref()
+            .map(AsHandleOrSocket::as_handle_or_socket)
+    }
+}
+
 impl<Inner: fmt::Debug> fmt::Debug for LayeredWriter<Inner> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut b = f.debug_struct("LayeredWriter");
         b.field("inner", &self.inner);
+        b.field("line_by_line", &self.line_by_line);
         b.finish()
     }
 }
@@ -236,3 +302,65 @@ impl<Inner> Drop for LayeredWriter<Inner> {
         assert!(self.inner.is_none(), "stream was not closed or abandoned");
     }
 }
+
+#[cfg(unix)]
+#[test]
+fn test_layered_writer_try_as_fd() {
+    let (stream, _peer) = std::os::unix::net::UnixStream::pair().unwrap();
+    let mut writer = LayeredWriter::new(stream);
+    assert!(writer.try_as_fd().is_some());
+    writer.abandon();
+    assert!(writer.try_as_fd().is_none());
+}
+
+#[test]
+fn test_layered_writer_line_by_line() {
+    use crate::auto_flush_writer::FlushCounter;
+
+    let mut writer = LayeredWriter::new(FlushCounter::default());
+    writer.write_all(b"one\n").unwrap();
+    writer.flush_with_status(Status::active()).unwrap();
+    assert!(writer.get_ref().flushes.is_empty());
+    writer.abandon();
+
+    let mut writer = LayeredWriter::line_by_line(FlushCounter::default());
+    writer.write_all(b"one\n").unwrap();
+    writer.flush_with_status(Status::active()).unwrap();
+    writer.write_all(b"two\n").unwrap();
+    writer.flush_with_status(Status::push()).unwrap();
+    assert_eq!(writer.get_ref().flushes, [4, 8]);
+    writer.close().unwrap();
+}
+
+#[cfg(feature = "terminal-io")]
+#[test]
+fn test_layered_writer_maybe_terminal() {
+    struct FakeTerminal(Vec<u8>);
+    impl Write for FakeTerminal {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.write(buf)
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+    impl terminal_io::Terminal for FakeTerminal {}
+    impl terminal_io::WriteTerminal for FakeTerminal {
+        fn color_support(&self) -> terminal_io::TerminalColorSupport {
+            terminal_io::TerminalColorSupport::Monochrome
+        }
+        fn color_preference(&self) -> bool {
+            false
+        }
+        fn is_output_terminal(&self) -> bool {
+            true
+        }
+    }
+
+    let mut writer = LayeredWriter::maybe_terminal(FakeTerminal(Vec::new()));
+    assert!(writer.line_by_line);
+    writer.abandon();
+    let mut writer = LayeredWriter::new(FakeTerminal(Vec::new()));
+    assert!(!writer.line_by_line);
+    writer.abandon();
+}
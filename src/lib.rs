@@ -1,51 +1,371 @@
 //! I/O traits extending [`std::io::Read`] and [`std::io::Write`].
+//!
+//! # `no_std`
+//!
+//! The `std` feature is enabled by default. Without it, this crate is
+//! `no_std`, and provides only [`Status`], [`Activity`], and the
+//! [`Bufferable`] trait, so that the status model can be used on targets
+//! without `std::io`. [`ReadLayered`] and [`WriteLayered`] extend
+//! `std::io::Read` and `std::io::Write`, so they, and all the readers,
+//! writers, and duplexers, require `std`, as do the optional features other
+//! than `serde`.
 
 #![deny(missing_docs)]
+#![forbid(unsafe_code)]
+// These lints suggest `Result::inspect_err` and `io::Error::other`, which
+// would raise the minimum supported Rust version.
+#![allow(clippy::manual_inspect, clippy::io_other_error)]
+#![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(can_vector, feature(can_vector))]
 #![cfg_attr(write_all_vectored, feature(write_all_vectored))]
 #![cfg_attr(target_os = "wasi", feature(wasi_ext))]
 
+extern crate alloc;
+
+#[cfg(feature = "std")]
+mod adler32_writer;
+#[cfg(feature = "futures-io")]
+mod async_layered_reader;
 #[cfg(feature = "futures-io")]
 mod async_read_layered;
 #[cfg(feature = "futures-io")]
 mod async_write_layered;
+#[cfg(feature = "std")]
+mod at_least_reader;
+#[cfg(any(feature = "flate2", feature = "ruzstd"))]
+mod auto_decompress_reader;
+#[cfg(feature = "std")]
+mod auto_flush_writer;
+#[cfg(feature = "std")]
+mod auto_push_writer;
+#[cfg(feature = "std")]
+mod buf_reader_layered;
+#[cfg(feature = "std")]
+mod buf_writer_layered;
 mod bufferable;
+#[cfg(feature = "std")]
+mod byte_count_reader;
+#[cfg(feature = "std")]
+mod bytes_with_status;
+#[cfg(feature = "std")]
+mod chain_layered;
+#[cfg(feature = "std")]
+mod chain_with_reader;
+#[cfg(feature = "std")]
+mod close_tracking_writer;
+#[cfg(feature = "std")]
+mod codec;
+#[cfg(feature = "std")]
+mod codec_reader;
+#[cfg(feature = "std")]
+mod codec_writer;
+#[cfg(feature = "std")]
+mod combine_reader;
+#[cfg(feature = "std")]
+mod compression;
+#[cfg(feature = "std")]
+mod content_length_reader;
+#[cfg(feature = "std")]
+mod content_length_writer;
+#[cfg(feature = "std")]
+mod copy;
+#[cfg(feature = "std")]
+mod counting_reader;
+#[cfg(feature = "std")]
+mod counting_writer;
+#[cfg(feature = "std")]
+mod debug_reader;
+#[cfg(feature = "std")]
+mod debug_writer;
+#[cfg(all(feature = "aes", feature = "ctr"))]
+mod decrypt_reader;
+#[cfg(feature = "std")]
 mod duplex_layered;
+#[cfg(feature = "std")]
+mod duplexer_halves;
+#[cfg(all(feature = "aes", feature = "ctr"))]
+mod encrypt_writer;
+#[cfg(feature = "std")]
+mod fixed_record_reader;
+#[cfg(feature = "std")]
+mod framed_reader;
+#[cfg(feature = "std")]
+mod framed_writer;
+#[cfg(feature = "flate2")]
+mod gz_decode_reader;
+#[cfg(feature = "flate2")]
+mod gz_encode_writer;
+#[cfg(feature = "std")]
+mod half_close;
+#[cfg(feature = "digest")]
+mod hash_reader;
+#[cfg(feature = "digest")]
+mod hash_writer;
+#[cfg(feature = "std")]
+mod heartbeat_filter_reader;
+#[cfg(feature = "std")]
+mod heartbeat_writer;
+#[cfg(feature = "std")]
+mod indent_writer;
+#[cfg(feature = "std")]
+mod interval_push_reader;
+#[cfg(feature = "std")]
 mod layered_duplexer;
+#[cfg(feature = "std")]
 mod layered_reader;
+#[cfg(feature = "std")]
 mod layered_writer;
+#[cfg(feature = "std")]
+mod line_codec;
+#[cfg(feature = "std")]
+mod lines_with_status;
+#[cfg(feature = "log")]
+mod logging_reader;
+#[cfg(feature = "log")]
+mod logging_writer;
+#[cfg(feature = "std")]
+mod magic_reader;
+#[cfg(feature = "std")]
+mod mark_reader;
+#[cfg(feature = "std")]
+mod max_line_reader;
+#[cfg(feature = "std")]
+mod multi_writer;
+#[cfg(feature = "std")]
+mod ndjson_reader;
+#[cfg(feature = "std")]
+mod packet_reader;
+#[cfg(feature = "std")]
+mod pad_writer;
+#[cfg(feature = "std")]
+mod pattern_push_reader;
+#[cfg(feature = "std")]
+mod per_record_file_writer;
+#[cfg(feature = "std")]
+mod position_reader;
+#[cfg(feature = "std")]
+mod prompting_reader;
+#[cfg(feature = "std")]
 mod read_layered;
+#[cfg(feature = "std")]
+mod record_writer;
+#[cfg(feature = "std")]
+mod rle_writer;
+#[cfg(feature = "std")]
+mod sequenced_writer;
+#[cfg(feature = "std")]
+mod skip_reader;
+#[cfg(feature = "std")]
+mod skip_writer;
+#[cfg(feature = "std")]
 mod slice_reader;
+#[cfg(feature = "std")]
+mod stats_reader;
+#[cfg(feature = "std")]
+mod stats_writer;
 mod status;
+#[cfg(feature = "std")]
+mod tail_capture_writer;
+#[cfg(feature = "std")]
+mod take_layered;
+#[cfg(feature = "std")]
+mod tee_reader;
+#[cfg(feature = "std")]
+mod throttled_writer;
+#[cfg(feature = "std")]
+mod timestamp_writer;
 #[cfg(feature = "tokio")]
 mod tokio_read_layered;
 #[cfg(feature = "tokio")]
 mod tokio_write_layered;
+#[cfg(feature = "std")]
+mod transcript_reader;
+#[cfg(feature = "std")]
+mod transform_tee_writer;
+#[cfg(feature = "std")]
+mod trim_trailing_reader;
+#[cfg(feature = "std")]
+mod utf8_boundary_writer;
+#[cfg(feature = "std")]
+mod wrap_writer;
+#[cfg(feature = "std")]
 mod write_layered;
 
+#[cfg(feature = "std")]
+pub use adler32_writer::Adler32Writer;
+#[cfg(feature = "futures-io")]
+pub use async_layered_reader::AsyncLayeredReader;
 #[cfg(feature = "futures-io")]
 pub use async_read_layered::{default_poll_read, default_poll_read_vectored, AsyncReadLayered};
 #[cfg(feature = "futures-io")]
 pub use async_write_layered::{default_poll_write_vectored, AsyncWriteLayered};
+#[cfg(feature = "std")]
+pub use at_least_reader::AtLeastReader;
+#[cfg(any(feature = "flate2", feature = "ruzstd"))]
+pub use auto_decompress_reader::AutoDecompressReader;
+#[cfg(feature = "std")]
+pub use auto_flush_writer::AutoFlushWriter;
+#[cfg(feature = "std")]
+pub use auto_push_writer::{AutoPushWriter, Clock};
+#[cfg(feature = "std")]
+pub use buf_reader_layered::BufReaderLayered;
+#[cfg(feature = "std")]
+pub use buf_writer_layered::BufWriterLayered;
 pub use bufferable::{default_suggested_buffer_size, Bufferable};
-pub use duplex_layered::HalfDuplexLayered;
+#[cfg(feature = "std")]
+pub use byte_count_reader::ByteCountReader;
+#[cfg(feature = "std")]
+pub use bytes_with_status::BytesWithStatus;
+#[cfg(feature = "std")]
+pub use chain_layered::{chain_layered, ChainLayered};
+#[cfg(feature = "std")]
+pub use chain_with_reader::ChainWithReader;
+#[cfg(feature = "std")]
+pub use close_tracking_writer::CloseTrackingWriter;
+#[cfg(feature = "std")]
+pub use codec::{Decoder, Encoder};
+#[cfg(feature = "std")]
+pub use codec_reader::CodecReader;
+#[cfg(feature = "std")]
+pub use codec_writer::CodecWriter;
+#[cfg(feature = "std")]
+pub use combine_reader::CombineReader;
+#[cfg(feature = "std")]
+pub use compression::{Compressor, Decompressor, IdentityCodec};
+#[cfg(feature = "std")]
+pub use content_length_reader::ContentLengthReader;
+#[cfg(feature = "std")]
+pub use content_length_writer::ContentLengthWriter;
+#[cfg(feature = "std")]
+pub use copy::copy;
+#[cfg(feature = "std")]
+pub use counting_reader::CountingReader;
+#[cfg(feature = "std")]
+pub use counting_writer::CountingWriter;
+#[cfg(feature = "std")]
+pub use debug_reader::DebugReader;
+#[cfg(feature = "std")]
+pub use debug_writer::DebugWriter;
+#[cfg(all(feature = "aes", feature = "ctr"))]
+pub use decrypt_reader::DecryptReader;
+#[cfg(feature = "futures-io")]
+pub use duplex_layered::AsyncHalfDuplexLayered;
+#[cfg(feature = "std")]
+pub use duplex_layered::{splice, HalfDuplexLayered};
+#[cfg(feature = "std")]
+pub use duplexer_halves::{ReadHalf, ReuniteError, WriteHalf};
+#[cfg(all(feature = "aes", feature = "ctr"))]
+pub use encrypt_writer::EncryptWriter;
+#[cfg(feature = "std")]
+pub use fixed_record_reader::FixedRecordReader;
+#[cfg(feature = "std")]
+pub use framed_reader::FramedReader;
+#[cfg(feature = "std")]
+pub use framed_writer::FramedWriter;
+#[cfg(feature = "flate2")]
+pub use gz_decode_reader::GzDecodeReader;
+#[cfg(feature = "flate2")]
+pub use gz_encode_writer::GzEncodeWriter;
+#[cfg(feature = "std")]
+pub use half_close::HalfClose;
+#[cfg(feature = "digest")]
+pub use hash_reader::HashReader;
+#[cfg(feature = "digest")]
+pub use hash_writer::HashWriter;
+#[cfg(feature = "std")]
+pub use heartbeat_filter_reader::HeartbeatFilterReader;
+#[cfg(feature = "std")]
+pub use heartbeat_writer::HeartbeatWriter;
+#[cfg(feature = "std")]
+pub use indent_writer::IndentWriter;
+#[cfg(feature = "std")]
+pub use interval_push_reader::IntervalPushReader;
+#[cfg(feature = "std")]
 pub use layered_duplexer::LayeredDuplexer;
+#[cfg(feature = "std")]
 pub use layered_reader::LayeredReader;
+#[cfg(feature = "std")]
 pub use layered_writer::LayeredWriter;
-#[cfg(can_vector)]
-pub use read_layered::default_is_read_vectored;
+#[cfg(feature = "std")]
+pub use line_codec::LineCodec;
+#[cfg(feature = "std")]
+pub use lines_with_status::LinesWithStatus;
+#[cfg(feature = "log")]
+pub use logging_reader::LoggingReader;
+#[cfg(feature = "log")]
+pub use logging_writer::LoggingWriter;
+#[cfg(feature = "std")]
+pub use magic_reader::MagicReader;
+#[cfg(feature = "std")]
+pub use mark_reader::MarkReader;
+#[cfg(feature = "std")]
+pub use max_line_reader::MaxLineReader;
+#[cfg(feature = "std")]
+pub use multi_writer::MultiWriter;
+#[cfg(feature = "std")]
+pub use ndjson_reader::NdjsonReader;
+#[cfg(feature = "std")]
+pub use packet_reader::PacketReader;
+#[cfg(feature = "std")]
+pub use pad_writer::PadWriter;
+#[cfg(feature = "std")]
+pub use pattern_push_reader::PatternPushReader;
+#[cfg(feature = "std")]
+pub use per_record_file_writer::PerRecordFileWriter;
+#[cfg(feature = "std")]
+pub use position_reader::PositionReader;
+#[cfg(feature = "std")]
+pub use prompting_reader::PromptingReader;
+#[cfg(feature = "std")]
 pub use read_layered::{
-    default_read, default_read_exact_using_status, default_read_to_end, default_read_to_string,
-    default_read_vectored, to_std_io_read_result, ReadLayered,
+    default_is_read_vectored, default_read, default_read_exact_using_status,
+    default_read_exact_vectored_using_status, default_read_to_end, default_read_to_end_with_status,
+    default_read_to_string, default_read_uninit_with_status, default_read_vectored,
+    default_read_vectored_eager_with_status, to_std_io_read_result, ReadLayered,
 };
+#[cfg(feature = "std")]
+pub use record_writer::{replay, RecordWriter, WriteOp};
+#[cfg(feature = "std")]
+pub use rle_writer::RleWriter;
+#[cfg(feature = "std")]
+pub use sequenced_writer::SequencedWriter;
+#[cfg(feature = "std")]
+pub use skip_reader::SkipReader;
+#[cfg(feature = "std")]
+pub use skip_writer::SkipWriter;
+#[cfg(feature = "std")]
 pub use slice_reader::SliceReader;
+#[cfg(feature = "std")]
+pub use stats_reader::{ReadStats, StatsReader};
+#[cfg(feature = "std")]
+pub use stats_writer::{StatsWriter, WriteStats};
 pub use status::{Activity, Status};
+#[cfg(feature = "std")]
+pub use tail_capture_writer::TailCaptureWriter;
+#[cfg(feature = "std")]
+pub use take_layered::TakeLayered;
+#[cfg(feature = "std")]
+pub use tee_reader::TeeReader;
+#[cfg(feature = "std")]
+pub use throttled_writer::ThrottledWriter;
+#[cfg(feature = "std")]
+pub use timestamp_writer::TimestampWriter;
 #[cfg(feature = "tokio")]
 pub use tokio_read_layered::{tokio_default_poll_read, TokioReadLayered};
 #[cfg(feature = "tokio")]
 pub use tokio_write_layered::{tokio_default_poll_write_vectored, TokioWriteLayered};
-#[cfg(can_vector)]
-pub use write_layered::default_is_write_vectored;
-#[cfg(write_all_vectored)]
-pub use write_layered::default_write_all_vectored;
-pub use write_layered::{default_write_all, default_write_vectored, WriteLayered};
+#[cfg(feature = "std")]
+pub use transcript_reader::TranscriptReader;
+#[cfg(feature = "std")]
+pub use transform_tee_writer::TransformTeeWriter;
+#[cfg(feature = "std")]
+pub use trim_trailing_reader::TrimTrailingReader;
+#[cfg(feature = "std")]
+pub use utf8_boundary_writer::Utf8BoundaryWriter;
+#[cfg(feature = "std")]
+pub use wrap_writer::WrapWriter;
+#[cfg(feature = "std")]
+pub use write_layered::{
+    default_is_write_vectored, default_write_all, default_write_all_vectored,
+    default_write_vectored, WriteLayered,
+};
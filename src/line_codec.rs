@@ -0,0 +1,93 @@
+use crate::{Decoder, Encoder};
+use std::io;
+
+/// A [`Decoder`] and [`Encoder`] for newline-terminated lines.
+///
+/// Decoded frames don't include the `\n`. At the end of the stream, a final
+/// line with no `\n` is decoded as a frame too.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LineCodec;
+
+impl LineCodec {
+    /// Construct a new `LineCodec`.
+    #[inline]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Decoder for LineCodec {
+    fn decode(&mut self, src: &mut Vec<u8>) -> io::Result<Option<Vec<u8>>> {
+        Ok(src.iter().position(|b| *b == b'\n').map(|newline| {
+            let mut line: Vec<u8> = src.drain(..=newline).collect();
+            line.pop();
+            line
+        }))
+    }
+
+    fn decode_eof(&mut self, src: &mut Vec<u8>) -> io::Result<Option<Vec<u8>>> {
+        match self.decode(src)? {
+            Some(line) => Ok(Some(line)),
+            None if src.is_empty() => Ok(None),
+            None => Ok(Some(std::mem::take(src))),
+        }
+    }
+}
+
+impl Encoder for LineCodec {
+    fn encode(&mut self, frame: &[u8], dst: &mut Vec<u8>) -> io::Result<()> {
+        dst.extend_from_slice(frame);
+        dst.push(b'\n');
+        Ok(())
+    }
+}
+
+#[test]
+fn test_line_codec_round_trip() {
+    use crate::{FramedReader, FramedWriter, ReadLayered, SliceReader, Status, WriteLayered};
+    use std::io::Write;
+
+    let mut writer = FramedWriter::new(io::Cursor::new(Vec::new()), LineCodec::new());
+    writer.write_all(b"hel").unwrap();
+    writer.write_all(b"lo").unwrap();
+    writer.flush_with_status(Status::push()).unwrap();
+    writer.write_all(b"world").unwrap();
+    writer.close().unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"hello\nworld\n");
+
+    let mut reader = FramedReader::new(
+        SliceReader::new(writer.get_ref().get_ref()),
+        LineCodec::new(),
+    );
+    let mut buf = [0; 3];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (3, Status::active())
+    );
+    assert_eq!(&buf, b"hel");
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (2, Status::push())
+    );
+    assert_eq!(&buf[..2], b"lo");
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (3, Status::active())
+    );
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (2, Status::push())
+    );
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (0, Status::End));
+}
+
+#[test]
+fn test_line_codec_unterminated() {
+    use crate::{FramedReader, SliceReader};
+    use std::io::Read;
+
+    let mut reader = FramedReader::new(SliceReader::new(b"a\nb\nc"), LineCodec::new());
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "abc");
+}
@@ -0,0 +1,180 @@
+use crate::{default_suggested_buffer_size, ReadLayered, Status};
+use std::fmt;
+use std::io;
+
+/// An iterator over the lines of a [`ReadLayered`], with the status of each
+/// line.
+///
+/// This is returned by [`ReadLayered::lines_with_status`]. Like
+/// [`BufRead::lines`], each line is returned without its `\n` or `\r\n`,
+/// and a line which isn't valid UTF-8 is an `io::ErrorKind::InvalidData`
+/// error.
+///
+/// Input is read into an internal buffer, which is scanned for newlines. The
+/// status of a line is the status of the read which completed it, if no
+/// buffered data follows it, and `Status::active()` otherwise. When the line
+/// ends all the buffered data and the read reported `Status::active()`, more
+/// is read before the line is returned, so that the last line of the stream
+/// carries `Status::End`. Pushes aren't read past, so this doesn't block
+/// waiting for more interactive input.
+///
+/// [`BufRead::lines`]: std::io::BufRead::lines
+pub struct LinesWithStatus<Inner> {
+    inner: Inner,
+    buf: Vec<u8>,
+    status: Status,
+    done: bool,
+}
+
+impl<Inner: ReadLayered> LinesWithStatus<Inner> {
+    pub(crate) fn new(inner: Inner) -> Self {
+        Self {
+            inner,
+            buf: Vec::new(),
+            status: Status::active(),
+            done: false,
+        }
+    }
+
+    /// Consume this `LinesWithStatus` and return the inner stream.
+    ///
+    /// Any buffered input which hasn't been returned yet is discarded.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+
+    /// Read more input into the buffer.
+    fn fill(&mut self) -> io::Result<()> {
+        let chunk =
+            default_suggested_buffer_size(&self.inner).max(self.inner.minimum_buffer_size());
+        let start = self.buf.len();
+        self.buf.resize(start + chunk, 0);
+        let result = self.inner.read_with_status(&mut self.buf[start..]);
+        let (size, status) = match result {
+            Ok(size_and_status) => size_and_status,
+            Err(e) => {
+                self.buf.truncate(start);
+                return Err(e);
+            }
+        };
+        self.buf.truncate(start + size);
+        self.status = status;
+        Ok(())
+    }
+
+    /// Remove `len` bytes from the front of the buffer and return them as a
+    /// line, with the status to report.
+    fn take_line(&mut self, len: usize) -> io::Result<(String, Status)> {
+        let mut line: Vec<u8> = self.buf.drain(..len).collect();
+        if line.last() == Some(&b'\n') {
+            line.pop();
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+        }
+        let status = if self.buf.is_empty() {
+            self.status
+        } else {
+            Status::active()
+        };
+        self.done = status.is_end();
+        let line = String::from_utf8(line)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "line is not valid UTF-8"))?;
+        Ok((line, status))
+    }
+}
+
+impl<Inner: ReadLayered> Iterator for LinesWithStatus<Inner> {
+    type Item = io::Result<(String, Status)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.done {
+                return None;
+            }
+            match self.buf.iter().position(|b| *b == b'\n') {
+                Some(newline)
+                    if newline + 1 < self.buf.len() || self.status != Status::active() =>
+                {
+                    return Some(self.take_line(newline + 1));
+                }
+                Some(newline) => {
+                    // Read ahead, but don't wait for data that isn't there.
+                    if let Err(e) = self.fill() {
+                        return Some(Err(e));
+                    }
+                    if self.buf.len() == newline + 1 {
+                        return Some(self.take_line(newline + 1));
+                    }
+                }
+                None if self.status.is_end() => {
+                    if self.buf.is_empty() {
+                        self.done = true;
+                        return None;
+                    }
+                    return Some(self.take_line(self.buf.len()));
+                }
+                _ => {
+                    if let Err(e) = self.fill() {
+                        return Some(Err(e));
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for LinesWithStatus<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("LinesWithStatus");
+        b.field("inner", &self.inner);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_lines_with_status() {
+    use crate::{IntervalPushReader, SliceReader};
+
+    let inner = SliceReader::new(b"first\r\nsecond\nthird\n");
+    let lines: Vec<_> = inner.lines_with_status().map(Result::unwrap).collect();
+    assert_eq!(
+        lines,
+        [
+            ("first".to_owned(), Status::active()),
+            ("second".to_owned(), Status::active()),
+            ("third".to_owned(), Status::End),
+        ]
+    );
+
+    // Pushes are reported on the lines they complete.
+    let inner = IntervalPushReader::new(SliceReader::new(b"ab\ncd\nef"), 3);
+    let lines: Vec<_> = inner.lines_with_status().map(Result::unwrap).collect();
+    assert_eq!(
+        lines,
+        [
+            ("ab".to_owned(), Status::push()),
+            ("cd".to_owned(), Status::push()),
+            ("ef".to_owned(), Status::End),
+        ]
+    );
+}
+
+#[test]
+fn test_lines_with_status_end_after_data() {
+    use crate::LayeredReader;
+
+    // `LayeredReader` reports the end with a separate empty read, which is
+    // read ahead so that the last line still carries `Status::End`.
+    let input = io::Cursor::new(b"one\ntwo\n".to_vec());
+    let mut lines = LayeredReader::new(input).lines_with_status();
+    assert_eq!(
+        lines.next().unwrap().unwrap(),
+        ("one".to_owned(), Status::active())
+    );
+    assert_eq!(
+        lines.next().unwrap().unwrap(),
+        ("two".to_owned(), Status::End)
+    );
+    assert!(lines.next().is_none());
+}
@@ -0,0 +1,201 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    ReadLayered, Status,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Adapts a [`ReadLayered`] to log each operation on it with the [`log`]
+/// crate, for debugging.
+///
+/// Each read is logged at the `trace` level, with the number of bytes read
+/// and the status, or the error. Abandoning the stream is logged at the
+/// `debug` level. Data and statuses are passed through unchanged.
+///
+/// [`log`]: https://docs.rs/log
+pub struct LoggingReader<Inner> {
+    inner: Inner,
+    target: String,
+}
+
+impl<Inner: ReadLayered> LoggingReader<Inner> {
+    /// Construct a new `LoggingReader` which wraps `inner` and logs with the
+    /// target `"layered_io"`.
+    pub fn new(inner: Inner) -> Self {
+        Self::with_target(inner, "layered_io")
+    }
+
+    /// Construct a new `LoggingReader` which wraps `inner` and logs with the
+    /// target `target`, so that it can be told apart from other streams.
+    pub fn with_target(inner: Inner, target: impl Into<String>) -> Self {
+        Self {
+            inner,
+            target: target.into(),
+        }
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Reads made directly on the underlying reader aren't logged.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `LoggingReader` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+
+    fn log_result(&self, op: &str, requested: usize, result: &io::Result<(usize, Status)>) {
+        match result {
+            Ok((size, status)) => log::trace!(
+                target: &self.target,
+                "{}: {} of {} bytes, {:?}",
+                op,
+                size,
+                requested,
+                status
+            ),
+            Err(e) => log::trace!(target: &self.target, "{}: error: {}", op, e),
+        }
+    }
+}
+
+impl<Inner: ReadLayered> ReadLayered for LoggingReader<Inner> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        let result = self.inner.read_with_status(buf);
+        self.log_result("read_with_status", buf.len(), &result);
+        result
+    }
+
+    fn read_vectored_with_status(
+        &mut self,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> io::Result<(usize, Status)> {
+        let result = self.inner.read_vectored_with_status(bufs);
+        let requested = bufs.iter().map(|b| b.len()).sum();
+        self.log_result("read_vectored_with_status", requested, &result);
+        result
+    }
+
+    fn peek(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        let result = self.inner.peek(buf);
+        self.log_result("peek", buf.len(), &result);
+        result
+    }
+
+    #[inline]
+    fn minimum_buffer_size(&self) -> usize {
+        self.inner.minimum_buffer_size()
+    }
+}
+
+impl<Inner: ReadLayered> Bufferable for LoggingReader<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        log::debug!(target: &self.target, "abandon");
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: ReadLayered> Read for LoggingReader<Inner> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for LoggingReader<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("LoggingReader");
+        b.field("inner", &self.inner);
+        b.field("target", &self.target);
+        b.finish()
+    }
+}
+
+/// Install a logger which records every message, and return the messages
+/// logged so far with the target `target`.
+#[cfg(test)]
+pub(crate) fn logged(target: &str) -> Vec<String> {
+    use std::sync::{Mutex, Once};
+
+    struct TestLogger;
+
+    static MESSAGES: Mutex<Vec<(String, String)>> = Mutex::new(Vec::new());
+    static INIT: Once = Once::new();
+
+    impl log::Log for TestLogger {
+        fn enabled(&self, _metadata: &log::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record<'_>) {
+            let message = format!("{} {}", record.level(), record.args());
+            let mut messages = MESSAGES.lock().unwrap();
+            messages.push((record.target().to_owned(), message));
+        }
+
+        fn flush(&self) {}
+    }
+
+    INIT.call_once(|| {
+        log::set_logger(&TestLogger).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+    });
+    let messages = MESSAGES.lock().unwrap();
+    messages
+        .iter()
+        .filter(|(t, _)| t == target)
+        .map(|(_, message)| message.clone())
+        .collect()
+}
+
+#[test]
+fn test_logging_reader() {
+    use crate::SliceReader;
+
+    let target = "test_logging_reader";
+    logged(target);
+    let mut reader = LoggingReader::with_target(SliceReader::new(b"hello"), target);
+    let mut buf = [0; 3];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (3, Status::active())
+    );
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (2, Status::End));
+    reader.abandon();
+    assert_eq!(
+        logged(target),
+        [
+            "TRACE read_with_status: 3 of 3 bytes, Open(Active)",
+            "TRACE read_with_status: 2 of 3 bytes, End",
+            "DEBUG abandon",
+        ]
+    );
+}
@@ -0,0 +1,162 @@
+use crate::{Bufferable, Status, WriteLayered};
+use std::fmt;
+use std::io::{self, IoSlice, Write};
+
+/// Adapts a [`WriteLayered`] to log each operation on it with the [`log`]
+/// crate, for debugging.
+///
+/// Each write and flush is logged at the `trace` level, with the number of
+/// bytes written or the status, or the error. Closing and abandoning the
+/// stream are logged at the `debug` level. Data and statuses are passed
+/// through unchanged.
+///
+/// [`log`]: https://docs.rs/log
+pub struct LoggingWriter<Inner> {
+    inner: Inner,
+    target: String,
+}
+
+impl<Inner: WriteLayered> LoggingWriter<Inner> {
+    /// Construct a new `LoggingWriter` which wraps `inner` and logs with the
+    /// target `"layered_io"`.
+    pub fn new(inner: Inner) -> Self {
+        Self::with_target(inner, "layered_io")
+    }
+
+    /// Construct a new `LoggingWriter` which wraps `inner` and logs with the
+    /// target `target`, so that it can be told apart from other streams.
+    pub fn with_target(inner: Inner, target: impl Into<String>) -> Self {
+        Self {
+            inner,
+            target: target.into(),
+        }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Writes made directly to the underlying writer aren't logged.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `LoggingWriter` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+
+    fn log_write(&self, op: &str, requested: usize, result: &io::Result<usize>) {
+        match result {
+            Ok(size) => log::trace!(
+                target: &self.target,
+                "{}: {} of {} bytes",
+                op,
+                size,
+                requested
+            ),
+            Err(e) => log::trace!(target: &self.target, "{}: error: {}", op, e),
+        }
+    }
+}
+
+impl<Inner: WriteLayered> WriteLayered for LoggingWriter<Inner> {
+    fn close(&mut self) -> io::Result<()> {
+        let result = self.inner.close();
+        match &result {
+            Ok(()) => log::debug!(target: &self.target, "close"),
+            Err(e) => log::debug!(target: &self.target, "close: error: {}", e),
+        }
+        result
+    }
+
+    fn flush_with_status(&mut self, status: Status) -> io::Result<()> {
+        let result = self.inner.flush_with_status(status);
+        match &result {
+            Ok(()) => log::trace!(target: &self.target, "flush_with_status({:?})", status),
+            Err(e) => log::trace!(
+                target: &self.target,
+                "flush_with_status({:?}): error: {}",
+                status,
+                e
+            ),
+        }
+        result
+    }
+}
+
+impl<Inner: WriteLayered> Bufferable for LoggingWriter<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        log::debug!(target: &self.target, "abandon");
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: WriteLayered> Write for LoggingWriter<Inner> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let result = self.inner.write(buf);
+        self.log_write("write", buf.len(), &result);
+        result
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        let result = self.inner.write_vectored(bufs);
+        let requested = bufs.iter().map(|b| b.len()).sum();
+        self.log_write("write_vectored", requested, &result);
+        result
+    }
+
+    #[cfg(can_vector)]
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let result = self.inner.flush();
+        match &result {
+            Ok(()) => log::trace!(target: &self.target, "flush"),
+            Err(e) => log::trace!(target: &self.target, "flush: error: {}", e),
+        }
+        result
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for LoggingWriter<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("LoggingWriter");
+        b.field("inner", &self.inner);
+        b.field("target", &self.target);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_logging_writer() {
+    use crate::logging_reader::logged;
+
+    let target = "test_logging_writer";
+    logged(target);
+    let mut writer = LoggingWriter::with_target(io::Cursor::new(Vec::new()), target);
+    writer.write_all(b"hello").unwrap();
+    writer.flush_with_status(Status::push()).unwrap();
+    writer.close().unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"hello");
+    assert_eq!(
+        logged(target),
+        [
+            "TRACE write: 5 of 5 bytes",
+            "TRACE flush_with_status(Open(Push))",
+            "DEBUG close",
+        ]
+    );
+}
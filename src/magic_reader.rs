@@ -0,0 +1,199 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    ReadLayered, Status,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Adapts a [`ReadLayered`] to check that it begins with an expected magic
+/// number.
+///
+/// The magic number is read and checked on the first read. If it doesn't
+/// match, or if the stream ends before it's complete, the read fails with
+/// `io::ErrorKind::InvalidData` and the inner stream is abandoned.
+pub struct MagicReader<Inner> {
+    inner: Inner,
+    magic: Vec<u8>,
+    keep_magic: bool,
+    checked: bool,
+    replay_pos: usize,
+    status_after_magic: Status,
+}
+
+impl<Inner: ReadLayered> MagicReader<Inner> {
+    /// Construct a new `MagicReader` which wraps `inner`, checks that it
+    /// begins with `magic`, and returns the data following the magic number.
+    pub fn expect(inner: Inner, magic: &[u8]) -> Self {
+        Self {
+            inner,
+            magic: magic.to_vec(),
+            keep_magic: false,
+            checked: false,
+            replay_pos: 0,
+            status_after_magic: Status::active(),
+        }
+    }
+
+    /// Like [`MagicReader::expect`], but the magic number is included in the
+    /// data returned, followed by the rest of the stream.
+    pub fn expect_and_keep(inner: Inner, magic: &[u8]) -> Self {
+        Self {
+            keep_magic: true,
+            ..Self::expect(inner, magic)
+        }
+    }
+
+    /// Consume this `MagicReader` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+
+    fn check(&mut self) -> io::Result<()> {
+        let mut found = vec![0; self.magic.len()];
+        let mut len = 0;
+        while len < found.len() {
+            let (size, status) = self.inner.read_with_status(&mut found[len..])?;
+            len += size;
+            self.status_after_magic = status;
+            if status.is_end() {
+                break;
+            }
+        }
+        if found[..len] != self.magic[..] {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "stream does not begin with the expected magic number",
+            ));
+        }
+        self.checked = true;
+        if !self.keep_magic {
+            self.replay_pos = self.magic.len();
+        }
+        Ok(())
+    }
+}
+
+impl<Inner: ReadLayered> ReadLayered for MagicReader<Inner> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        if !self.checked {
+            if let Err(e) = self.check() {
+                self.abandon();
+                return Err(e);
+            }
+        }
+
+        if self.replay_pos < self.magic.len() {
+            let size = (self.magic.len() - self.replay_pos).min(buf.len());
+            buf[..size].copy_from_slice(&self.magic[self.replay_pos..self.replay_pos + size]);
+            self.replay_pos += size;
+            return Ok((size, self.replay_status()));
+        }
+        if self.status_after_magic.is_end() {
+            return Ok((0, Status::End));
+        }
+
+        self.inner.read_with_status(buf)
+    }
+
+    #[inline]
+    fn minimum_buffer_size(&self) -> usize {
+        self.inner.minimum_buffer_size()
+    }
+}
+
+impl<Inner> MagicReader<Inner> {
+    fn replay_status(&self) -> Status {
+        if self.replay_pos == self.magic.len() && self.status_after_magic.is_end() {
+            Status::End
+        } else {
+            Status::active()
+        }
+    }
+}
+
+impl<Inner: ReadLayered> Bufferable for MagicReader<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon();
+        self.checked = true;
+        self.replay_pos = self.magic.len();
+        self.status_after_magic = Status::End;
+    }
+}
+
+impl<Inner: ReadLayered> Read for MagicReader<Inner> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for MagicReader<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("MagicReader");
+        b.field("inner", &self.inner);
+        b.field("magic", &self.magic);
+        b.field("keep_magic", &self.keep_magic);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_magic_reader_match() {
+    use crate::SliceReader;
+
+    let mut reader = MagicReader::expect(SliceReader::new(b"\x7fELF rest"), b"\x7fELF");
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, " rest");
+
+    let mut reader = MagicReader::expect_and_keep(SliceReader::new(b"\x7fELF rest"), b"\x7fELF");
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "\x7fELF rest");
+}
+
+#[test]
+fn test_magic_reader_spanning_reads() {
+    use crate::IntervalPushReader;
+
+    // `IntervalPushReader` splits the magic number across inner reads.
+    let inner = IntervalPushReader::new(crate::SliceReader::new(b"MAGIC!"), 2);
+    let mut reader = MagicReader::expect_and_keep(inner, b"MAGIC");
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "MAGIC!");
+}
+
+#[test]
+fn test_magic_reader_mismatch() {
+    use crate::SliceReader;
+
+    let mut reader = MagicReader::expect(SliceReader::new(b"GIF89a"), b"\x89PNG");
+    let mut buf = [0; 8];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+
+    let mut reader = MagicReader::expect(SliceReader::new(b"\x89P"), b"\x89PNG");
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+}
@@ -0,0 +1,224 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    ReadLayered, Status,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Adapts a [`ReadLayered`] to support bounded backtracking with
+/// [`MarkReader::mark`] and [`MarkReader::reset`].
+///
+/// After `mark(limit)`, up to `limit` bytes read are retained, along with the
+/// status each read returned, so that `reset` can replay them. If more than
+/// `limit` bytes are read after the mark, the mark is invalidated.
+pub struct MarkReader<Inner> {
+    inner: Inner,
+    marked: bool,
+    limit: usize,
+    buffer: Vec<u8>,
+    /// The end offsets in `buffer` of each read, with its status.
+    segments: Vec<(usize, Status)>,
+    replay_pos: Option<usize>,
+}
+
+impl<Inner: ReadLayered> MarkReader<Inner> {
+    /// Construct a new `MarkReader` which wraps `inner`.
+    pub fn new(inner: Inner) -> Self {
+        Self {
+            inner,
+            marked: false,
+            limit: 0,
+            buffer: Vec::new(),
+            segments: Vec::new(),
+            replay_pos: None,
+        }
+    }
+
+    /// Mark the current position, retaining up to `limit` subsequently read
+    /// bytes so that [`MarkReader::reset`] can return to it.
+    ///
+    /// This replaces any previous mark.
+    pub fn mark(&mut self, limit: usize) {
+        match self.replay_pos.take() {
+            // Keep the bytes which haven't been replayed yet.
+            Some(pos) => {
+                self.buffer.drain(..pos);
+                self.segments.retain(|(end, _)| *end > pos);
+                for (end, _) in &mut self.segments {
+                    *end -= pos;
+                }
+                self.replay_pos = Some(0);
+            }
+            None => {
+                self.buffer.clear();
+                self.segments.clear();
+            }
+        }
+        self.limit = limit;
+        self.marked = self.buffer.len() <= limit;
+    }
+
+    /// Return to the most recent mark, so that the bytes read since then are
+    /// read again.
+    ///
+    /// This fails if there is no mark, or if more bytes than the mark's limit
+    /// have been read since it was set.
+    pub fn reset(&mut self) -> io::Result<()> {
+        if !self.marked {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "no valid mark to reset to",
+            ));
+        }
+        if !self.buffer.is_empty() {
+            self.replay_pos = Some(0);
+        }
+        Ok(())
+    }
+
+    /// Consume this `MarkReader` and return the inner stream.
+    ///
+    /// Any bytes not yet replayed are discarded.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+
+    fn replay(&mut self, pos: usize, buf: &mut [u8]) -> (usize, Status) {
+        let (end, status) = *self
+            .segments
+            .iter()
+            .find(|(end, _)| *end > pos)
+            .expect("replay position within buffer");
+        let size = (end - pos).min(buf.len());
+        buf[..size].copy_from_slice(&self.buffer[pos..pos + size]);
+        let pos = pos + size;
+        self.replay_pos = if pos == self.buffer.len() {
+            None
+        } else {
+            Some(pos)
+        };
+        if pos == end {
+            (size, status)
+        } else {
+            (size, Status::active())
+        }
+    }
+}
+
+impl<Inner: ReadLayered> ReadLayered for MarkReader<Inner> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        if let Some(pos) = self.replay_pos {
+            return Ok(self.replay(pos, buf));
+        }
+
+        let (size, status) = self.inner.read_with_status(buf)?;
+        if self.marked && size != 0 {
+            if self.buffer.len() + size > self.limit {
+                self.marked = false;
+                self.buffer.clear();
+                self.segments.clear();
+            } else {
+                self.buffer.extend_from_slice(&buf[..size]);
+                self.segments.push((self.buffer.len(), status));
+            }
+        }
+        Ok((size, status))
+    }
+
+    #[inline]
+    fn minimum_buffer_size(&self) -> usize {
+        self.inner.minimum_buffer_size()
+    }
+}
+
+impl<Inner: ReadLayered> Bufferable for MarkReader<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon();
+        self.marked = false;
+        self.buffer.clear();
+        self.segments.clear();
+        self.replay_pos = None;
+    }
+}
+
+impl<Inner: ReadLayered> Read for MarkReader<Inner> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for MarkReader<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("MarkReader");
+        b.field("inner", &self.inner);
+        b.field("marked", &self.marked);
+        b.field("limit", &self.limit);
+        b.field("buffered", &self.buffer.len());
+        b.finish()
+    }
+}
+
+#[test]
+fn test_mark_reader() {
+    let mut reader = MarkReader::new(crate::SliceReader::new(b"hello world"));
+    let mut buf = [0; 6];
+    reader.mark(16);
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (6, Status::active())
+    );
+    assert_eq!(&buf, b"hello ");
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (5, Status::End));
+    assert_eq!(&buf[..5], b"world");
+
+    reader.reset().unwrap();
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "hello world");
+}
+
+#[test]
+fn test_mark_reader_remark_during_replay() {
+    let mut reader = MarkReader::new(crate::SliceReader::new(b"hello world"));
+    let mut buf = [0; 3];
+    reader.mark(8);
+    reader.read_exact_using_status(&mut buf).unwrap();
+    reader.reset().unwrap();
+    reader.read_exact_using_status(&mut buf[..1]).unwrap();
+    reader.mark(8);
+    reader.read_exact_using_status(&mut buf).unwrap();
+    assert_eq!(&buf, b"ell");
+    reader.reset().unwrap();
+    reader.read_exact_using_status(&mut buf).unwrap();
+    assert_eq!(&buf, b"ell");
+}
+
+#[test]
+fn test_mark_reader_invalidated() {
+    let mut reader = MarkReader::new(crate::SliceReader::new(b"hello world"));
+    let mut buf = [0; 6];
+    assert!(reader.reset().is_err());
+    reader.mark(4);
+    reader.read_with_status(&mut buf).unwrap();
+    assert_eq!(
+        reader.reset().unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+}
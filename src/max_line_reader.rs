@@ -0,0 +1,144 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    ReadLayered, Status,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Adapts a [`ReadLayered`] to fail if a line is longer than a limit.
+///
+/// This guards consumers which accumulate whole lines against unterminated
+/// input. Data passes through unchanged, with the inner status. If more than
+/// `max_line_len` bytes are read without a `\n`, the read fails with
+/// `io::ErrorKind::InvalidData` and the stream is abandoned. The limit
+/// doesn't count the `\n` itself.
+pub struct MaxLineReader<Inner> {
+    inner: Inner,
+    max_line_len: usize,
+    line_len: usize,
+}
+
+impl<Inner: ReadLayered> MaxLineReader<Inner> {
+    /// Construct a new `MaxLineReader` which wraps `inner` and fails on lines
+    /// longer than `max_line_len` bytes.
+    pub fn new(inner: Inner, max_line_len: usize) -> Self {
+        Self {
+            inner,
+            max_line_len,
+            line_len: 0,
+        }
+    }
+
+    /// Consume this `MaxLineReader` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+
+    fn line_too_long(&mut self) -> io::Error {
+        self.abandon();
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("line longer than {} bytes", self.max_line_len),
+        )
+    }
+}
+
+impl<Inner: ReadLayered> ReadLayered for MaxLineReader<Inner> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        let (size, status) = self.inner.read_with_status(buf)?;
+        for byte in &buf[..size] {
+            if *byte == b'\n' {
+                self.line_len = 0;
+            } else {
+                self.line_len += 1;
+                if self.line_len > self.max_line_len {
+                    return Err(self.line_too_long());
+                }
+            }
+        }
+        Ok((size, status))
+    }
+
+    #[inline]
+    fn minimum_buffer_size(&self) -> usize {
+        self.inner.minimum_buffer_size()
+    }
+}
+
+impl<Inner: ReadLayered> Bufferable for MaxLineReader<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon();
+        self.line_len = 0;
+    }
+}
+
+impl<Inner: ReadLayered> Read for MaxLineReader<Inner> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for MaxLineReader<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("MaxLineReader");
+        b.field("inner", &self.inner);
+        b.field("max_line_len", &self.max_line_len);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_max_line_reader() {
+    use crate::{IntervalPushReader, SliceReader};
+
+    let inner = IntervalPushReader::new(SliceReader::new(b"short\nlines\nonly\n"), 3);
+    let mut reader = MaxLineReader::new(inner, 5);
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "short\nlines\nonly\n");
+}
+
+#[test]
+fn test_max_line_reader_too_long() {
+    use crate::{IntervalPushReader, SliceReader};
+
+    // The long line is detected both within a read and across reads.
+    let mut reader = MaxLineReader::new(SliceReader::new(b"ok\ntoo long\nok\n"), 5);
+    let mut buf = [0; 32];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+
+    let inner = IntervalPushReader::new(SliceReader::new(b"ok\ntoo long\nok\n"), 4);
+    let mut reader = MaxLineReader::new(inner, 5);
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (4, Status::push())
+    );
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (4, Status::push())
+    );
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+}
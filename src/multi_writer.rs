@@ -0,0 +1,147 @@
+use crate::{Bufferable, Status, WriteLayered};
+use std::fmt;
+use std::io::{self, Write};
+
+/// Writes to any number of [`WriteLayered`]s, for example to send the same
+/// log output to a file and a socket.
+///
+/// Each write is written in full to every sink in turn, so partial writes
+/// don't leave the sinks out of sync. Flushes, statuses, and
+/// [`WriteLayered::close`] are forwarded to every sink, even if some of them
+/// fail, and the first error is returned.
+pub struct MultiWriter {
+    sinks: Vec<Box<dyn WriteLayered>>,
+}
+
+impl MultiWriter {
+    /// Construct a new `MultiWriter` which writes to each of `sinks`.
+    pub fn new(sinks: Vec<Box<dyn WriteLayered>>) -> Self {
+        Self { sinks }
+    }
+
+    /// Add another sink, which receives everything written from now on.
+    pub fn push(&mut self, sink: Box<dyn WriteLayered>) {
+        self.sinks.push(sink);
+    }
+
+    /// Gets references to the sinks.
+    pub fn get_ref(&self) -> &[Box<dyn WriteLayered>] {
+        &self.sinks
+    }
+
+    /// Gets mutable references to the sinks.
+    ///
+    /// It is inadvisable to directly write to the sinks.
+    pub fn get_mut(&mut self) -> &mut [Box<dyn WriteLayered>] {
+        &mut self.sinks
+    }
+
+    /// Consume this `MultiWriter` and return the sinks.
+    pub fn into_inner(self) -> Vec<Box<dyn WriteLayered>> {
+        self.sinks
+    }
+
+    /// Call `f` on every sink, and return the first error.
+    fn for_each(
+        &mut self,
+        mut f: impl FnMut(&mut dyn WriteLayered) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let mut result = Ok(());
+        for sink in &mut self.sinks {
+            let r = f(&mut **sink);
+            result = result.and(r);
+        }
+        result
+    }
+}
+
+impl WriteLayered for MultiWriter {
+    fn close(&mut self) -> io::Result<()> {
+        self.for_each(|sink| sink.close())
+    }
+
+    fn flush_with_status(&mut self, status: Status) -> io::Result<()> {
+        self.for_each(|sink| sink.flush_with_status(status))
+    }
+}
+
+impl Bufferable for MultiWriter {
+    #[inline]
+    fn abandon(&mut self) {
+        for sink in &mut self.sinks {
+            sink.abandon();
+        }
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.sinks
+            .iter()
+            .map(|sink| sink.suggested_buffer_size())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl Write for MultiWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for sink in &mut self.sinks {
+            sink.write_all(buf)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.for_each(|sink| sink.flush())
+    }
+}
+
+impl fmt::Debug for MultiWriter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("MultiWriter");
+        b.field("sinks", &self.sinks.len());
+        b.finish()
+    }
+}
+
+#[test]
+fn test_multi_writer() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    // A sink which records what's written to it, with `|` marking each
+    // push and `$` marking the end.
+    struct Sink(Rc<RefCell<io::Cursor<Vec<u8>>>>);
+
+    impl WriteLayered for Sink {
+        fn close(&mut self) -> io::Result<()> {
+            self.0.borrow_mut().write_all(b"$")
+        }
+    }
+
+    impl Bufferable for Sink {
+        fn abandon(&mut self) {}
+    }
+
+    impl Write for Sink {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.0.borrow_mut().write_all(b"|")
+        }
+    }
+
+    let a = Rc::new(RefCell::new(io::Cursor::new(Vec::new())));
+    let b = Rc::new(RefCell::new(io::Cursor::new(Vec::new())));
+    let mut writer = MultiWriter::new(vec![Box::new(Sink(a.clone())), Box::new(Sink(b.clone()))]);
+    writer.write_all(b"hello ").unwrap();
+    writer.flush_with_status(Status::active()).unwrap();
+    writer.write_all(b"world").unwrap();
+    writer.flush_with_status(Status::push()).unwrap();
+    writer.write_all(b"!").unwrap();
+    writer.flush_with_status(Status::End).unwrap();
+    assert_eq!(a.borrow().get_ref(), b"hello world|!$");
+    assert_eq!(b.borrow().get_ref(), b"hello world|!$");
+}
@@ -0,0 +1,124 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    Decoder, FramedReader, ReadLayered, Status,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Adapts a [`ReadLayered`] to return the records of a newline-delimited
+/// JSON stream one at a time.
+///
+/// Each record is a line, including its `\n`, and its end is reported as a
+/// `Status::push()`, so that a JSON parser can process one record at a time.
+/// Lines which are empty or contain only whitespace are skipped. A final
+/// line with no `\n` is returned as a record too. The records themselves
+/// aren't parsed.
+pub struct NdjsonReader<Inner> {
+    inner: FramedReader<Inner, NdjsonDecoder>,
+}
+
+impl<Inner: ReadLayered> NdjsonReader<Inner> {
+    /// Construct a new `NdjsonReader` which wraps `inner`.
+    pub fn new(inner: Inner) -> Self {
+        Self {
+            inner: FramedReader::new(inner, NdjsonDecoder),
+        }
+    }
+
+    /// Consume this `NdjsonReader` and return the inner stream.
+    ///
+    /// Any buffered input which hasn't been returned yet is discarded.
+    pub fn into_inner(self) -> Inner {
+        self.inner.into_inner()
+    }
+}
+
+/// A [`Decoder`] for lines which skips blank lines.
+struct NdjsonDecoder;
+
+impl Decoder for NdjsonDecoder {
+    fn decode(&mut self, src: &mut Vec<u8>) -> io::Result<Option<Vec<u8>>> {
+        while let Some(newline) = src.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = src.drain(..=newline).collect();
+            if !line.iter().all(u8::is_ascii_whitespace) {
+                return Ok(Some(line));
+            }
+        }
+        Ok(None)
+    }
+
+    fn decode_eof(&mut self, src: &mut Vec<u8>) -> io::Result<Option<Vec<u8>>> {
+        match self.decode(src)? {
+            Some(line) => Ok(Some(line)),
+            None if src.iter().all(u8::is_ascii_whitespace) => {
+                src.clear();
+                Ok(None)
+            }
+            None => Ok(Some(std::mem::take(src))),
+        }
+    }
+}
+
+impl<Inner: ReadLayered> ReadLayered for NdjsonReader<Inner> {
+    #[inline]
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        self.inner.read_with_status(buf)
+    }
+
+    #[inline]
+    fn minimum_buffer_size(&self) -> usize {
+        self.inner.minimum_buffer_size()
+    }
+}
+
+impl<Inner: ReadLayered> Bufferable for NdjsonReader<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon()
+    }
+}
+
+impl<Inner: ReadLayered> Read for NdjsonReader<Inner> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for NdjsonReader<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("NdjsonReader");
+        b.field("inner", &self.inner);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_ndjson_reader() {
+    use crate::SliceReader;
+
+    let input = b"{\"a\":1}\n\n{\"b\":[2,3]}\n  \n{\"c\":null}";
+    let mut reader = NdjsonReader::new(SliceReader::new(input));
+    let mut buf = [0; 32];
+    for expected in [&b"{\"a\":1}\n"[..], b"{\"b\":[2,3]}\n", b"{\"c\":null}"] {
+        let (size, status) = reader.read_with_status(&mut buf).unwrap();
+        assert_eq!(&buf[..size], expected);
+        assert_eq!(status, Status::push());
+    }
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (0, Status::End));
+}
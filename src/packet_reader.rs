@@ -0,0 +1,160 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    ReadLayered, Status,
+};
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// A [`ReadLayered`] which returns data from a list of packets, for testing
+/// consumers of `read_with_status` and `read_vectored_with_status`.
+///
+/// Each read returns data from at most one packet, even when a vectored read
+/// provides enough space for more; a vectored read scatters one packet across
+/// its buffers. The end of each packet is reported as a `Status::push()`,
+/// except for the last packet, whose end is reported as `Status::End`.
+pub struct PacketReader {
+    packets: VecDeque<Vec<u8>>,
+    pos: usize,
+}
+
+impl PacketReader {
+    /// Construct a new `PacketReader` which returns `packets` in order.
+    pub fn new(packets: Vec<Vec<u8>>) -> Self {
+        Self {
+            packets: packets.into(),
+            pos: 0,
+        }
+    }
+
+    /// Advance past `size` bytes of the current packet, and return the status
+    /// to report.
+    fn consume(&mut self, size: usize) -> Status {
+        self.pos += size;
+        match self.packets.front() {
+            Some(packet) if self.pos == packet.len() => {
+                self.packets.pop_front();
+                self.pos = 0;
+                if self.packets.is_empty() {
+                    Status::End
+                } else {
+                    Status::push()
+                }
+            }
+            Some(_) => Status::active(),
+            None => Status::End,
+        }
+    }
+}
+
+impl ReadLayered for PacketReader {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        let size = match self.packets.front() {
+            Some(packet) => {
+                let size = (packet.len() - self.pos).min(buf.len());
+                buf[..size].copy_from_slice(&packet[self.pos..self.pos + size]);
+                size
+            }
+            None => 0,
+        };
+        Ok((size, self.consume(size)))
+    }
+
+    fn read_vectored_with_status(
+        &mut self,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> io::Result<(usize, Status)> {
+        let mut size = 0;
+        if let Some(packet) = self.packets.front() {
+            let mut rest = &packet[self.pos..];
+            for buf in bufs {
+                if rest.is_empty() {
+                    break;
+                }
+                let n = rest.len().min(buf.len());
+                buf[..n].copy_from_slice(&rest[..n]);
+                rest = &rest[n..];
+                size += n;
+            }
+        }
+        Ok((size, self.consume(size)))
+    }
+}
+
+impl Bufferable for PacketReader {
+    #[inline]
+    fn abandon(&mut self) {
+        self.packets.clear();
+        self.pos = 0;
+    }
+}
+
+impl Read for PacketReader {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl fmt::Debug for PacketReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("PacketReader");
+        b.field("packets", &self.packets.len());
+        b.field("pos", &self.pos);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_packet_reader() {
+    let mut reader = PacketReader::new(vec![b"hello".to_vec(), b"world".to_vec()]);
+    let mut buf = [0; 3];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (3, Status::active())
+    );
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (2, Status::push())
+    );
+    assert_eq!(&buf[..2], b"lo");
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "world");
+}
+
+#[test]
+fn test_packet_reader_vectored() {
+    let mut reader = PacketReader::new(vec![b"abcde".to_vec(), b"fg".to_vec()]);
+    let mut a = [0; 2];
+    let mut b = [0; 8];
+    let mut bufs = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)];
+    assert_eq!(
+        reader.read_vectored_with_status(&mut bufs).unwrap(),
+        (5, Status::push())
+    );
+    assert_eq!(&a, b"ab");
+    assert_eq!(&b[..3], b"cde");
+
+    let mut bufs = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)];
+    assert_eq!(
+        reader.read_vectored_with_status(&mut bufs).unwrap(),
+        (2, Status::End)
+    );
+    assert_eq!(&a, b"fg");
+}
@@ -0,0 +1,128 @@
+use crate::{Bufferable, WriteLayered};
+use std::fmt;
+use std::io::{self, Read, Write};
+
+/// Adapts a [`WriteLayered`] to pad its output to a fixed total size.
+///
+/// On [`WriteLayered::close`], the output is padded with a fill byte up to
+/// the configured total size. If more than that many bytes were written, the
+/// inner stream is abandoned and `close` fails.
+pub struct PadWriter<Inner> {
+    inner: Inner,
+    total: u64,
+    fill: u8,
+    written: u64,
+}
+
+impl<Inner: WriteLayered> PadWriter<Inner> {
+    /// Construct a new `PadWriter` which wraps `inner`, and pads its output
+    /// with `fill` bytes up to `total` bytes on close.
+    pub fn new(inner: Inner, total: u64, fill: u8) -> Self {
+        Self {
+            inner,
+            total,
+            fill,
+            written: 0,
+        }
+    }
+
+    /// Return the number of bytes written so far, not including padding.
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Bytes written directly to the underlying writer aren't counted.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `PadWriter` and return the inner stream, without padding.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+}
+
+impl<Inner: WriteLayered> WriteLayered for PadWriter<Inner> {
+    fn close(&mut self) -> io::Result<()> {
+        if self.written > self.total {
+            self.inner.abandon();
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "written data exceeds the padded size",
+            ));
+        }
+        let padding = self.total - self.written;
+        io::copy(&mut io::repeat(self.fill).take(padding), &mut self.inner)?;
+        self.written = self.total;
+        self.inner.close()
+    }
+}
+
+impl<Inner: WriteLayered> Bufferable for PadWriter<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: WriteLayered> Write for PadWriter<Inner> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let size = self.inner.write(buf)?;
+        self.written += size as u64;
+        Ok(size)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for PadWriter<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("PadWriter");
+        b.field("inner", &self.inner);
+        b.field("total", &self.total);
+        b.field("written", &self.written);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_pad_writer_under() {
+    let mut writer = PadWriter::new(io::Cursor::new(Vec::new()), 8, b'.');
+    writer.write_all(b"abc").unwrap();
+    writer.close().unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"abc.....");
+}
+
+#[test]
+fn test_pad_writer_exact() {
+    let mut writer = PadWriter::new(io::Cursor::new(Vec::new()), 3, b'.');
+    writer.write_all(b"abc").unwrap();
+    writer.close().unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"abc");
+}
+
+#[test]
+fn test_pad_writer_over() {
+    let mut writer = PadWriter::new(io::Cursor::new(Vec::new()), 2, b'.');
+    writer.write_all(b"abc").unwrap();
+    assert_eq!(
+        writer.close().unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+}
@@ -0,0 +1,209 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    ReadLayered, Status,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Adapts a [`ReadLayered`] to report a push after each occurrence of a byte
+/// pattern.
+///
+/// This generalizes line-by-line pushes to multi-byte terminators such as
+/// `b"\n\n"`. Occurrences are found even when they span reads from the inner
+/// stream. Reads are shortened so that they end exactly after each
+/// occurrence, where the status is rewritten to `Status::push()`; data read
+/// past an occurrence is held and returned by subsequent reads. A
+/// `Status::End` from the inner stream is always preserved.
+pub struct PatternPushReader<Inner> {
+    inner: Inner,
+    pattern: Vec<u8>,
+    /// The most recent bytes returned, up to the length of the pattern.
+    window: Vec<u8>,
+    /// Data read from the inner stream after an occurrence of the pattern,
+    /// with the status of that read.
+    held: Vec<u8>,
+    held_status: Status,
+}
+
+impl<Inner: ReadLayered> PatternPushReader<Inner> {
+    /// Construct a new `PatternPushReader` which wraps `inner` and reports a
+    /// push after each occurrence of `pattern`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pattern` is empty.
+    pub fn new(inner: Inner, pattern: impl Into<Vec<u8>>) -> Self {
+        let pattern = pattern.into();
+        assert!(!pattern.is_empty(), "pattern must be non-empty");
+        Self {
+            window: Vec::with_capacity(pattern.len()),
+            inner,
+            pattern,
+            held: Vec::new(),
+            held_status: Status::active(),
+        }
+    }
+
+    /// Consume this `PatternPushReader` and return the inner stream.
+    ///
+    /// Any held data is discarded.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+
+    /// Scan `data` for the end of an occurrence of the pattern, and return
+    /// the length of the prefix of `data` up to and including it.
+    fn scan(&mut self, data: &[u8]) -> Option<usize> {
+        for (i, byte) in data.iter().enumerate() {
+            if self.window.len() == self.pattern.len() {
+                self.window.remove(0);
+            }
+            self.window.push(*byte);
+            if self.window == self.pattern {
+                // Occurrences don't overlap.
+                self.window.clear();
+                return Some(i + 1);
+            }
+        }
+        None
+    }
+}
+
+impl<Inner: ReadLayered> ReadLayered for PatternPushReader<Inner> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        if !self.held.is_empty() {
+            let len = self.held.len().min(buf.len());
+            buf[..len].copy_from_slice(&self.held[..len]);
+            let (size, status) = match self.scan(&buf[..len]) {
+                Some(end) => (end, Status::push()),
+                None => (len, Status::active()),
+            };
+            self.held.drain(..size);
+            if self.held.is_empty() && (status == Status::active() || self.held_status.is_end()) {
+                return Ok((size, self.held_status));
+            }
+            return Ok((size, status));
+        }
+
+        let (size, status) = self.inner.read_with_status(buf)?;
+        match self.scan(&buf[..size]) {
+            Some(end) if end < size => {
+                self.held.extend_from_slice(&buf[end..size]);
+                self.held_status = status;
+                Ok((end, Status::push()))
+            }
+            Some(_) if !status.is_end() => Ok((size, Status::push())),
+            _ => Ok((size, status)),
+        }
+    }
+
+    #[inline]
+    fn minimum_buffer_size(&self) -> usize {
+        self.inner.minimum_buffer_size()
+    }
+}
+
+impl<Inner: ReadLayered> Bufferable for PatternPushReader<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon();
+        self.held.clear();
+        self.held_status = Status::End;
+    }
+}
+
+impl<Inner: ReadLayered> Read for PatternPushReader<Inner> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for PatternPushReader<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("PatternPushReader");
+        b.field("inner", &self.inner);
+        b.field("pattern", &self.pattern);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_pattern_push_reader_spanning_reads() {
+    use crate::LayeredReader;
+    use std::collections::VecDeque;
+
+    // A source which returns one chunk per read.
+    struct Chunks(VecDeque<&'static [u8]>);
+
+    impl Read for Chunks {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match self.0.pop_front() {
+                Some(chunk) => {
+                    buf[..chunk.len()].copy_from_slice(chunk);
+                    Ok(chunk.len())
+                }
+                None => Ok(0),
+            }
+        }
+    }
+
+    let chunks = Chunks(vec![&b"one\n"[..], b"\ntwo", b"\n\nthree"].into());
+    let mut reader = PatternPushReader::new(LayeredReader::new(chunks), &b"\n\n"[..]);
+    let mut buf = [0; 16];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (4, Status::active())
+    );
+    assert_eq!(&buf[..4], b"one\n");
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (1, Status::push())
+    );
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (3, Status::active())
+    );
+    assert_eq!(&buf[..3], b"two");
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (2, Status::push())
+    );
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "three");
+}
+
+#[test]
+fn test_pattern_push_reader_within_read() {
+    let inner = crate::SliceReader::new(b"a\n\nb\n\nc");
+    let mut reader = PatternPushReader::new(inner, &b"\n\n"[..]);
+    let mut buf = [0; 16];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (3, Status::push())
+    );
+    assert_eq!(&buf[..3], b"a\n\n");
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (3, Status::push())
+    );
+    assert_eq!(&buf[..3], b"b\n\n");
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (1, Status::End));
+    assert_eq!(buf[0], b'c');
+}
@@ -0,0 +1,147 @@
+use crate::{Bufferable, WriteLayered};
+use std::fmt;
+use std::io::{self, Write};
+
+/// A [`WriteLayered`] which writes each record to its own sink, such as a
+/// file, where records are delimited by pushes.
+///
+/// Data is buffered until a `flush_with_status(Status::push())`, or a plain
+/// [`Write::flush`], ends the record. Then a sink for it is opened by calling
+/// the factory function with the index of the record, counting from 0, and
+/// the record is written to it and the sink is closed. Ending a record which
+/// is empty doesn't open a sink. [`WriteLayered::close`] ends the last
+/// record.
+///
+/// If writing a record fails, its sink is abandoned and the record remains
+/// buffered, so a later flush tries again with a new sink.
+pub struct PerRecordFileWriter<F> {
+    factory: F,
+    record: Vec<u8>,
+    index: usize,
+    ended: bool,
+}
+
+impl<F: FnMut(usize) -> io::Result<Box<dyn WriteLayered>>> PerRecordFileWriter<F> {
+    /// Construct a new `PerRecordFileWriter` which calls `factory` to open a
+    /// sink for each record.
+    pub fn new(factory: F) -> Self {
+        Self {
+            factory,
+            record: Vec::new(),
+            index: 0,
+            ended: false,
+        }
+    }
+
+    /// Return the number of records written so far.
+    pub fn records_written(&self) -> usize {
+        self.index
+    }
+
+    fn check_open(&self) -> io::Result<()> {
+        if self.ended {
+            Err(stream_already_ended())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Write the buffered record to a new sink.
+    fn end_record(&mut self) -> io::Result<()> {
+        if self.record.is_empty() {
+            return Ok(());
+        }
+        let mut sink = (self.factory)(self.index)?;
+        if let Err(e) = sink.write_all(&self.record) {
+            sink.abandon();
+            return Err(e);
+        }
+        sink.close()?;
+        self.record.clear();
+        self.index += 1;
+        Ok(())
+    }
+}
+
+impl<F: FnMut(usize) -> io::Result<Box<dyn WriteLayered>>> WriteLayered for PerRecordFileWriter<F> {
+    fn close(&mut self) -> io::Result<()> {
+        self.check_open()?;
+        self.end_record()?;
+        self.ended = true;
+        Ok(())
+    }
+}
+
+impl<F> Bufferable for PerRecordFileWriter<F> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.record = Vec::new();
+        self.ended = true;
+    }
+}
+
+impl<F: FnMut(usize) -> io::Result<Box<dyn WriteLayered>>> Write for PerRecordFileWriter<F> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.check_open()?;
+        self.record.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    /// End the current record.
+    ///
+    /// A `flush_with_status(Status::push())` comes here by way of the
+    /// default [`WriteLayered::flush_with_status`].
+    fn flush(&mut self) -> io::Result<()> {
+        self.check_open()?;
+        self.end_record()
+    }
+}
+
+impl<F> fmt::Debug for PerRecordFileWriter<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("PerRecordFileWriter");
+        b.field("record", &self.record.len());
+        b.field("index", &self.index);
+        b.finish()
+    }
+}
+
+fn stream_already_ended() -> io::Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, "stream has already ended")
+}
+
+#[test]
+fn test_per_record_file_writer() {
+    use crate::{LayeredWriter, Status};
+    use std::fs::{self, File};
+
+    let dir = std::env::temp_dir().join(format!("layered-io-per-record-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = |index: usize| dir.join(format!("record{}.txt", index));
+
+    let mut writer = PerRecordFileWriter::new(|index| {
+        let file = File::create(path(index))?;
+        Ok(Box::new(LayeredWriter::new(file)) as Box<dyn WriteLayered>)
+    });
+    writer.write_all(b"first ").unwrap();
+    writer.write_all(b"record").unwrap();
+    writer.flush_with_status(Status::push()).unwrap();
+    writer.write_all(b"second record").unwrap();
+    writer.flush_with_status(Status::active()).unwrap();
+    writer.flush_with_status(Status::push()).unwrap();
+    writer.flush_with_status(Status::push()).unwrap();
+    writer.write_all(b"third, unterminated").unwrap();
+    writer.close().unwrap();
+    assert_eq!(writer.records_written(), 3);
+
+    let mut names: Vec<_> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    names.sort();
+    assert_eq!(names, ["record0.txt", "record1.txt", "record2.txt"]);
+    assert_eq!(fs::read_to_string(path(0)).unwrap(), "first record");
+    assert_eq!(fs::read_to_string(path(1)).unwrap(), "second record");
+    assert_eq!(fs::read_to_string(path(2)).unwrap(), "third, unterminated");
+    fs::remove_dir_all(&dir).unwrap();
+}
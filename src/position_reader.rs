@@ -0,0 +1,128 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    ReadLayered, Status,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Adapts a [`ReadLayered`] to track the line and column position of the
+/// data read from it.
+///
+/// Lines and columns are numbered from 1, and columns count bytes, so a
+/// multi-byte UTF-8 character counts as several columns.
+pub struct PositionReader<Inner> {
+    inner: Inner,
+    line: u64,
+    column: u64,
+    offset: u64,
+}
+
+impl<Inner: ReadLayered> PositionReader<Inner> {
+    /// Construct a new `PositionReader` which wraps `inner`.
+    pub fn new(inner: Inner) -> Self {
+        Self {
+            inner,
+            line: 1,
+            column: 1,
+            offset: 0,
+        }
+    }
+
+    /// Return the line number of the next byte to be read.
+    pub fn line(&self) -> u64 {
+        self.line
+    }
+
+    /// Return the column number of the next byte to be read.
+    pub fn column(&self) -> u64 {
+        self.column
+    }
+
+    /// Return the number of bytes read so far.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Consume this `PositionReader` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+}
+
+impl<Inner: ReadLayered> ReadLayered for PositionReader<Inner> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        let (size, status) = self.inner.read_with_status(buf)?;
+        let data = &buf[..size];
+        self.offset += size as u64;
+        match data.iter().rposition(|b| *b == b'\n') {
+            Some(last) => {
+                self.line += data.iter().filter(|b| **b == b'\n').count() as u64;
+                self.column = (size - last) as u64;
+            }
+            None => self.column += size as u64,
+        }
+        Ok((size, status))
+    }
+
+    #[inline]
+    fn minimum_buffer_size(&self) -> usize {
+        self.inner.minimum_buffer_size()
+    }
+}
+
+impl<Inner: ReadLayered> Bufferable for PositionReader<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon()
+    }
+}
+
+impl<Inner: ReadLayered> Read for PositionReader<Inner> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for PositionReader<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("PositionReader");
+        b.field("inner", &self.inner);
+        b.field("line", &self.line);
+        b.field("column", &self.column);
+        b.field("offset", &self.offset);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_position_reader() {
+    let mut reader = PositionReader::new(crate::SliceReader::new(b"ab\ncd\nefg\nh"));
+    let mut buf = [0; 4];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (4, Status::active())
+    );
+    assert_eq!((reader.line(), reader.column()), (2, 2));
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (4, Status::active())
+    );
+    assert_eq!((reader.line(), reader.column()), (3, 3));
+    assert_eq!(reader.offset(), 8);
+}
@@ -0,0 +1,119 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    ReadLayered, Status, WriteLayered,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Adapts a [`ReadLayered`] to write a prompt to a [`WriteLayered`] each time
+/// the reader reports a push.
+///
+/// This couples the two halves of a REPL-style interaction: when a read
+/// returns a `Status::push()`, such as at the end of a line from a
+/// line-by-line source, the prompt is written and pushed, asking for the
+/// next input. Data is returned unchanged. No prompt is written after
+/// `Status::End`, and none is written before the first read; write the
+/// initial prompt with [`PromptingReader::writer_mut`].
+pub struct PromptingReader<R, W> {
+    reader: R,
+    writer: W,
+    prompt: Vec<u8>,
+}
+
+impl<R: ReadLayered, W: WriteLayered> PromptingReader<R, W> {
+    /// Construct a new `PromptingReader` which reads from `reader` and writes
+    /// `prompt` to `writer` after each push.
+    pub fn new(reader: R, writer: W, prompt: impl Into<Vec<u8>>) -> Self {
+        Self {
+            reader,
+            writer,
+            prompt: prompt.into(),
+        }
+    }
+
+    /// Gets a mutable reference to the writer which prompts are written to.
+    pub fn writer_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Consume this `PromptingReader` and return the inner reader and writer.
+    pub fn into_inner(self) -> (R, W) {
+        (self.reader, self.writer)
+    }
+}
+
+impl<R: ReadLayered, W: WriteLayered> ReadLayered for PromptingReader<R, W> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        let (size, status) = self.reader.read_with_status(buf)?;
+        if status == Status::push() {
+            self.writer.write_all(&self.prompt)?;
+            self.writer.flush_with_status(Status::push())?;
+        }
+        Ok((size, status))
+    }
+
+    #[inline]
+    fn minimum_buffer_size(&self) -> usize {
+        self.reader.minimum_buffer_size()
+    }
+}
+
+impl<R: ReadLayered, W: WriteLayered> Bufferable for PromptingReader<R, W> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.reader.abandon();
+        self.writer.abandon();
+    }
+}
+
+impl<R: ReadLayered, W: WriteLayered> Read for PromptingReader<R, W> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<R: fmt::Debug, W: fmt::Debug> fmt::Debug for PromptingReader<R, W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("PromptingReader");
+        b.field("reader", &self.reader);
+        b.field("writer", &self.writer);
+        b.field("prompt", &String::from_utf8_lossy(&self.prompt));
+        b.finish()
+    }
+}
+
+#[test]
+fn test_prompting_reader() {
+    use crate::PacketReader;
+    use std::io::Write;
+
+    let lines = PacketReader::new(vec![b"1 + 1\n".to_vec(), b"quit\n".to_vec()]);
+    let mut reader = PromptingReader::new(lines, io::Cursor::new(Vec::new()), "> ");
+    reader.writer_mut().write_all(b"> ").unwrap();
+    let mut buf = [0; 16];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (6, Status::push())
+    );
+    assert_eq!(reader.writer_mut().get_ref(), b"> > ");
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (5, Status::End));
+    let (_, mut writer) = reader.into_inner();
+    writer.close().unwrap();
+    assert_eq!(writer.get_ref(), b"> > ");
+}
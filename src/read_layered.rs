@@ -1,9 +1,17 @@
-use super::{Bufferable, Status};
+use super::{
+    default_suggested_buffer_size, Activity, Bufferable, BytesWithStatus, ChainWithReader,
+    LinesWithStatus, Status, TakeLayered,
+};
 use std::io::{self, IoSliceMut, Read};
+use std::mem::MaybeUninit;
 
 /// An extension of [`Read`], with `read_with_status` and
 /// `read_vectored_with_status` which return status information and zero is not
 /// special-cased. It also allows streams to specify a `minimum_buffer_size`.
+///
+/// `ReadLayered` is object-safe, and [`Bufferable`]'s methods, such as
+/// `suggested_buffer_size` and `abandon`, are callable through a
+/// `dyn ReadLayered`.
 pub trait ReadLayered: Read + Bufferable {
     /// Like [`Read::read`], but also returns a `Status`.
     fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)>;
@@ -17,6 +25,41 @@ pub trait ReadLayered: Read + Bufferable {
         default_read_vectored_with_status(self, bufs)
     }
 
+    /// Like [`ReadLayered::read_with_status`], but reads into a buffer which
+    /// may be uninitialized.
+    ///
+    /// On success returning `(size, status)`, the first `size` elements of
+    /// `buf` have been initialized. The default implementation reads into a
+    /// zeroed buffer on the stack and copies the data into `buf`; streams
+    /// which copy from memory they already have can override it to avoid the
+    /// zeroing and copying.
+    ///
+    /// `ReadLayered` is a safe trait, so unsafe code must not rely on an
+    /// arbitrary implementation upholding this for soundness.
+    #[inline]
+    fn read_uninit_with_status(
+        &mut self,
+        buf: &mut [MaybeUninit<u8>],
+    ) -> io::Result<(usize, Status)> {
+        default_read_uninit_with_status(self, buf)
+    }
+
+    /// Like [`ReadLayered::read_vectored_with_status`], but keeps reading
+    /// until every buffer is full, a read returns no data, or a read reports a
+    /// push or end of stream.
+    ///
+    /// This may issue multiple reads, and so may block when a single read
+    /// wouldn't. If a read fails after some data has been read, the error is
+    /// returned, unless it's `Interrupted` or `WouldBlock`, which only ask
+    /// the caller to retry; in that case, the data read so far is returned.
+    #[inline]
+    fn read_vectored_eager_with_status(
+        &mut self,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> io::Result<(usize, Status)> {
+        default_read_vectored_eager_with_status(self, bufs)
+    }
+
     /// Like `Read::read_exact`, but uses `read_with_status` to avoid
     /// performing an extra `read` at the end.
     #[inline]
@@ -24,11 +67,111 @@ pub trait ReadLayered: Read + Bufferable {
         default_read_exact_using_status(self, buf)
     }
 
+    /// Like [`ReadLayered::read_exact_using_status`], but reads into multiple
+    /// buffers, using `read_vectored_with_status`.
+    #[inline]
+    fn read_exact_vectored_using_status(
+        &mut self,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> io::Result<Status> {
+        default_read_exact_vectored_using_status(self, bufs)
+    }
+
+    /// Like [`Read::read_to_end`], but first reserves `capacity_hint` bytes
+    /// in `buf`, to reduce reallocations when the approximate size of the
+    /// stream is known.
+    #[inline]
+    fn read_to_end_with_capacity(
+        &mut self,
+        buf: &mut Vec<u8>,
+        capacity_hint: usize,
+    ) -> io::Result<usize> {
+        buf.reserve(capacity_hint);
+        self.read_to_end(buf)
+    }
+
+    /// Like [`Read::read_to_end`], but stops at a push as well as at the end
+    /// of the stream, and returns the status it stopped at.
+    ///
+    /// This lets callers tell a stream which has ended apart from one which
+    /// has reached a push, such as a [`LayeredReader`] constructed with
+    /// [`LayeredReader::with_eos_as_push`] reaching the current end of a file.
+    ///
+    /// [`LayeredReader`]: crate::LayeredReader
+    /// [`LayeredReader::with_eos_as_push`]: crate::LayeredReader::with_eos_as_push
+    #[inline]
+    fn read_to_end_with_status(&mut self, buf: &mut Vec<u8>) -> io::Result<(usize, Status)> {
+        default_read_to_end_with_status(self, buf)
+    }
+
+    /// Like [`ReadLayered::read_with_status`], but leaves the data in the
+    /// stream, so that the next read returns it again.
+    ///
+    /// The status describes the end of the peeked data; for example, it's
+    /// `Status::End` if the peeked data extends to the end of the stream.
+    /// Streams which buffer data may fail to peek more than their
+    /// `suggested_buffer_size` bytes, and may peek less than requested.
+    ///
+    /// The default implementation fails with
+    /// [`io::ErrorKind::Unsupported`].
+    #[inline]
+    fn peek(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        let _ = buf;
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "peeking is not supported by this stream",
+        ))
+    }
+
     /// Some streams require a buffer of at least a certain size.
     #[inline]
     fn minimum_buffer_size(&self) -> usize {
         0
     }
+
+    /// Return a reader which reads this stream until it ends, then `sep`,
+    /// then `next`, reporting `Status::End` when `next` ends.
+    #[inline]
+    fn chain_with<B: ReadLayered>(self, sep: Vec<u8>, next: B) -> ChainWithReader<Self, B>
+    where
+        Self: Sized,
+    {
+        ChainWithReader::new(self, sep, next)
+    }
+
+    /// Return a reader which reads at most `limit` bytes from this stream,
+    /// reporting `Status::End` when the limit is reached.
+    ///
+    /// This is like [`Read::take`], but it preserves statuses. See
+    /// [`TakeLayered`]. To read a prefix without consuming the stream, call
+    /// it on a mutable reference, as in `(&mut reader).take_layered(limit)`.
+    #[inline]
+    fn take_layered(self, limit: u64) -> TakeLayered<Self>
+    where
+        Self: Sized,
+    {
+        TakeLayered::new(self, limit)
+    }
+
+    /// Return an iterator over the bytes of this stream, with the status of
+    /// each byte, so that consumers can see where pushes fall.
+    #[inline]
+    fn bytes_with_status(self) -> BytesWithStatus<Self>
+    where
+        Self: Sized,
+    {
+        BytesWithStatus::new(self)
+    }
+
+    /// Return an iterator over the lines of this stream, with the status of
+    /// each line, so that consumers can tell which line was the last.
+    #[inline]
+    fn lines_with_status(self) -> LinesWithStatus<Self>
+    where
+        Self: Sized,
+    {
+        LinesWithStatus::new(self)
+    }
 }
 
 /// Default implementation of [`Read::read`] in terms of
@@ -54,13 +197,39 @@ pub fn default_read_vectored<Inner: ReadLayered + ?Sized>(
 
 /// Default implementation of [`Read::read_to_end`] in terms of
 /// [`ReadLayered::read_with_status`].
-#[allow(clippy::indexing_slicing)]
+#[inline]
 pub fn default_read_to_end<Inner: ReadLayered + ?Sized>(
     inner: &mut Inner,
     buf: &mut Vec<u8>,
 ) -> io::Result<usize> {
+    read_to_end_until(inner, buf, false).map(|(size, _status)| size)
+}
+
+/// Default implementation of [`ReadLayered::read_to_end_with_status`] in
+/// terms of [`ReadLayered::read_with_status`].
+#[inline]
+pub fn default_read_to_end_with_status<Inner: ReadLayered + ?Sized>(
+    inner: &mut Inner,
+    buf: &mut Vec<u8>,
+) -> io::Result<(usize, Status)> {
+    read_to_end_until(inner, buf, true)
+}
+
+/// Read into `buf` until the end of the stream, or until a push if
+/// `stop_at_push` is set.
+#[allow(clippy::indexing_slicing)]
+fn read_to_end_until<Inner: ReadLayered + ?Sized>(
+    inner: &mut Inner,
+    buf: &mut Vec<u8>,
+    stop_at_push: bool,
+) -> io::Result<(usize, Status)> {
     let start_len = buf.len();
-    let buffer_size = inner.suggested_buffer_size();
+    // Streams which don't need buffering suggest a size of 0, but reading
+    // into an empty buffer here would never make progress.
+    let buffer_size = match inner.suggested_buffer_size() {
+        0 => default_suggested_buffer_size(inner),
+        size => size,
+    };
     let mut read_len = buffer_size;
     loop {
         let read_pos = buf.len();
@@ -76,13 +245,16 @@ pub fn default_read_to_end<Inner: ReadLayered + ?Sized>(
             Ok((size, status)) => {
                 buf.resize(read_pos + size, 0);
                 match status {
+                    Status::Open(Activity::Push) if stop_at_push => {
+                        return Ok((buf.len() - start_len, status))
+                    }
                     Status::Open(_) => {
                         read_len -= size;
                         if read_len < inner.minimum_buffer_size() {
                             read_len += buffer_size;
                         }
                     }
-                    Status::End => return Ok(buf.len() - start_len),
+                    Status::End => return Ok((buf.len() - start_len, status)),
                 }
             }
             Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
@@ -105,7 +277,7 @@ pub fn default_read_to_string<Inner: ReadLayered + ?Sized>(
     // avoids issues of undefined behavior for now.
     let mut vec = Vec::new();
     let size = inner.read_to_end(&mut vec)?;
-    let new = String::from_utf8(vec).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    let new = String::from_utf8(vec).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
     buf.push_str(&new);
     Ok(size)
 }
@@ -143,22 +315,171 @@ pub fn default_read_exact_using_status<Inner: ReadLayered + ?Sized>(
     }
 }
 
+/// Default implementation of
+/// [`ReadLayered::read_exact_vectored_using_status`] in terms of
+/// [`ReadLayered::read_vectored_with_status`].
+///
+/// Once a buffer has been partially filled, the rest of it is read with
+/// `read_with_status`, since an `IoSliceMut` can't be advanced in place. On
+/// success, this returns the status of the last read.
+#[allow(clippy::indexing_slicing)]
+pub fn default_read_exact_vectored_using_status<Inner: ReadLayered + ?Sized>(
+    inner: &mut Inner,
+    mut bufs: &mut [IoSliceMut<'_>],
+) -> io::Result<Status> {
+    let mut result_status = Status::active();
+    // The number of bytes already read into the first buffer.
+    let mut offset = 0;
+
+    while let Some(first) = bufs.first() {
+        if offset == first.len() {
+            let t = bufs;
+            bufs = &mut t[1..];
+            offset = 0;
+            continue;
+        }
+        let (mut size, status) = if offset == 0 {
+            inner.read_vectored_with_status(bufs)?
+        } else {
+            inner.read_with_status(&mut bufs[0][offset..])?
+        };
+        result_status = status;
+        while size != 0 {
+            let len = bufs[0].len() - offset;
+            if size < len {
+                offset += size;
+                break;
+            }
+            size -= len;
+            let t = bufs;
+            bufs = &mut t[1..];
+            offset = 0;
+        }
+        if status.is_end() {
+            break;
+        }
+    }
+
+    if bufs.iter().map(|b| b.len()).sum::<usize>() == offset {
+        Ok(result_status)
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "failed to fill whole buffers",
+        ))
+    }
+}
+
 /// Default implementation of [`ReadLayered::read_vectored_with_status`] in
 /// terms of [`ReadLayered::read_with_status`].
+///
+/// This reads into each non-empty buffer in turn, with one read per buffer,
+/// stopping after a read which doesn't fill its buffer or which reports a
+/// push or end of stream. If a read fails after some data has been read, the
+/// error is returned, unless it's `Interrupted` or `WouldBlock`, which only
+/// ask the caller to retry; in that case, the data read so far is returned.
 pub fn default_read_vectored_with_status<Inner: ReadLayered + ?Sized>(
     inner: &mut Inner,
     bufs: &mut [IoSliceMut<'_>],
 ) -> io::Result<(usize, Status)> {
-    let buf = bufs
-        .iter_mut()
-        .find(|b| !b.is_empty())
-        .map_or(&mut [][..], |b| &mut **b);
-    inner.read_with_status(buf)
+    let mut nonempty = bufs.iter_mut().filter(|b| !b.is_empty());
+    let first = match nonempty.next() {
+        Some(first) => first,
+        None => return inner.read_with_status(&mut []),
+    };
+
+    let (mut total, mut status) = inner.read_with_status(first)?;
+    if total != first.len() || status != Status::active() {
+        return Ok((total, status));
+    }
+    for buf in nonempty {
+        let size = match inner.read_with_status(buf) {
+            Ok((size, buf_status)) => {
+                status = buf_status;
+                size
+            }
+            Err(e) if is_retry(&e) => return Ok((total, Status::active())),
+            Err(e) => return Err(e),
+        };
+        total += size;
+        if size != buf.len() || status != Status::active() {
+            break;
+        }
+    }
+    Ok((total, status))
+}
+
+/// Test whether `e` only asks the caller to retry, so that a read which has
+/// already read some data can return it instead of the error, without the
+/// error being lost.
+pub(crate) fn is_retry(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock
+    )
+}
+
+/// Default implementation of [`ReadLayered::read_uninit_with_status`] in
+/// terms of [`ReadLayered::read_with_status`].
+///
+/// This reads into a zeroed buffer on the stack, of up to 8 KiB or the
+/// stream's `minimum_buffer_size`, whichever is larger, and copies the data
+/// read into `buf`, so it may read less than a larger `buf` could hold.
+#[allow(clippy::indexing_slicing)]
+pub fn default_read_uninit_with_status<Inner: ReadLayered + ?Sized>(
+    inner: &mut Inner,
+    buf: &mut [MaybeUninit<u8>],
+) -> io::Result<(usize, Status)> {
+    const STACK_BUFFER_SIZE: usize = 0x2000;
+
+    let len = buf
+        .len()
+        .min(STACK_BUFFER_SIZE.max(inner.minimum_buffer_size()));
+    let mut stack = [0; STACK_BUFFER_SIZE];
+    let mut heap;
+    let init = if len <= STACK_BUFFER_SIZE {
+        &mut stack[..len]
+    } else {
+        heap = vec![0; len];
+        &mut heap[..]
+    };
+    let (size, status) = inner.read_with_status(init)?;
+    for (dst, src) in buf.iter_mut().zip(&init[..size]) {
+        *dst = MaybeUninit::new(*src);
+    }
+    Ok((size, status))
+}
+
+/// Default implementation of [`ReadLayered::read_vectored_eager_with_status`]
+/// in terms of [`ReadLayered::read_with_status`].
+pub fn default_read_vectored_eager_with_status<Inner: ReadLayered + ?Sized>(
+    inner: &mut Inner,
+    bufs: &mut [IoSliceMut<'_>],
+) -> io::Result<(usize, Status)> {
+    let mut total = 0;
+    for buf in bufs.iter_mut() {
+        let mut filled = 0;
+        while filled < buf.len() {
+            let (size, status) = match inner.read_with_status(&mut buf[filled..]) {
+                Ok(size_and_status) => size_and_status,
+                Err(e) if total != 0 && is_retry(&e) => return Ok((total, Status::active())),
+                Err(e) => return Err(e),
+            };
+            filled += size;
+            total += size;
+            if size == 0 || status != Status::active() {
+                return Ok((total, status));
+            }
+        }
+    }
+    Ok((total, Status::active()))
 }
 
 /// Default implementation of [`Read::is_read_vectored`] accompanying
-/// [`default_read_vectored_with_status`].
-#[cfg(can_vector)]
+/// [`default_read_vectored`].
+///
+/// `Read::is_read_vectored` itself is unstable, but this function is
+/// available on stable Rust.
 pub fn default_is_read_vectored<Inner: ReadLayered + ?Sized>(_inner: &Inner) -> bool {
     false
 }
@@ -191,6 +512,19 @@ impl<R: ReadLayered> ReadLayered for Box<R> {
         self.as_mut().read_vectored_with_status(bufs)
     }
 
+    #[inline]
+    fn read_uninit_with_status(
+        &mut self,
+        buf: &mut [MaybeUninit<u8>],
+    ) -> io::Result<(usize, Status)> {
+        self.as_mut().read_uninit_with_status(buf)
+    }
+
+    #[inline]
+    fn peek(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        self.as_mut().peek(buf)
+    }
+
     #[inline]
     fn minimum_buffer_size(&self) -> usize {
         self.as_ref().minimum_buffer_size()
@@ -211,8 +545,435 @@ impl<R: ReadLayered> ReadLayered for &mut R {
         (**self).read_vectored_with_status(bufs)
     }
 
+    #[inline]
+    fn read_uninit_with_status(
+        &mut self,
+        buf: &mut [MaybeUninit<u8>],
+    ) -> io::Result<(usize, Status)> {
+        (**self).read_uninit_with_status(buf)
+    }
+
+    #[inline]
+    fn peek(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        (**self).peek(buf)
+    }
+
     #[inline]
     fn minimum_buffer_size(&self) -> usize {
         (**self).minimum_buffer_size()
     }
 }
+
+impl ReadLayered for io::Empty {
+    #[inline]
+    fn read_with_status(&mut self, _buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        Ok((0, Status::End))
+    }
+
+    #[inline]
+    fn read_vectored_with_status(
+        &mut self,
+        _bufs: &mut [IoSliceMut<'_>],
+    ) -> io::Result<(usize, Status)> {
+        Ok((0, Status::End))
+    }
+
+    #[inline]
+    fn read_uninit_with_status(
+        &mut self,
+        _buf: &mut [MaybeUninit<u8>],
+    ) -> io::Result<(usize, Status)> {
+        Ok((0, Status::End))
+    }
+
+    #[inline]
+    fn peek(&mut self, _buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        Ok((0, Status::End))
+    }
+}
+
+/// `Repeat` never ends, so reading it to the end, with
+/// [`Read::read_to_end`] or [`ReadLayered::read_to_end_with_status`], loops
+/// forever. Use [`ReadLayered::take_layered`] to bound it.
+impl ReadLayered for io::Repeat {
+    #[inline]
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        let size = self.read(buf)?;
+        Ok((size, Status::active()))
+    }
+
+    #[inline]
+    fn read_vectored_with_status(
+        &mut self,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> io::Result<(usize, Status)> {
+        let size = self.read_vectored(bufs)?;
+        Ok((size, Status::active()))
+    }
+
+    /// Reading from `Repeat` doesn't consume anything, so this is the same
+    /// as reading.
+    #[inline]
+    fn peek(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        self.read_with_status(buf)
+    }
+}
+
+#[test]
+fn test_dyn_read_layered() {
+    let mut input = crate::SliceReader::new(b"hello");
+    let reader: &dyn ReadLayered = &input;
+    assert_eq!(reader.suggested_buffer_size(), 0);
+    assert_eq!(reader.minimum_buffer_size(), 0);
+
+    let reader: &mut dyn ReadLayered = &mut input;
+    let mut buf = [0; 5];
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (5, Status::End));
+    reader.abandon();
+
+    let mut reader: Box<dyn ReadLayered> =
+        Box::new(crate::LayeredReader::new(io::Cursor::new(b"world")));
+    assert_eq!(
+        reader.suggested_buffer_size(),
+        crate::default_suggested_buffer_size(&*reader)
+    );
+    reader.abandon();
+}
+
+#[test]
+fn test_read_to_end_with_capacity() {
+    use crate::LayeredReader;
+
+    for hint in [0, 4, 11, 4096] {
+        let mut reader = LayeredReader::new(io::Cursor::new(b"hello world"));
+        let mut buf = b">".to_vec();
+        assert_eq!(
+            reader.read_to_end_with_capacity(&mut buf, hint).unwrap(),
+            11
+        );
+        assert_eq!(buf, b">hello world");
+        assert!(buf.capacity() > hint);
+    }
+}
+
+#[test]
+fn test_read_vectored_eager_with_status() {
+    use crate::LayeredReader;
+
+    // A source which returns at most 3 bytes per read.
+    struct Chunked<'a>(&'a [u8]);
+
+    impl Read for Chunked<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = buf.len().min(3);
+            self.0.read(&mut buf[..len])
+        }
+    }
+
+    let mut reader = LayeredReader::new(Chunked(b"hello world"));
+    let mut a = [0; 4];
+    let mut b = [0; 5];
+    let mut bufs = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)];
+    assert_eq!(
+        reader.read_vectored_eager_with_status(&mut bufs).unwrap(),
+        (9, Status::active())
+    );
+    assert_eq!(&a, b"hell");
+    assert_eq!(&b, b"o wor");
+
+    // The end of the stream is reported along with the last of the data.
+    let mut bufs = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)];
+    assert_eq!(
+        reader.read_vectored_eager_with_status(&mut bufs).unwrap(),
+        (2, Status::End)
+    );
+    assert_eq!(&a[..2], b"ld");
+}
+
+/// A source which returns at most 3 bytes per read, and then fails with
+/// `error`.
+#[cfg(test)]
+struct FailAfter {
+    data: &'static [u8],
+    error: io::ErrorKind,
+}
+
+#[cfg(test)]
+impl Read for FailAfter {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.data.is_empty() {
+            return Err(io::Error::new(self.error, "read failed"));
+        }
+        let len = buf.len().min(3);
+        self.data.read(&mut buf[..len])
+    }
+}
+
+#[test]
+fn test_default_read_vectored_with_status_error() {
+    use crate::LayeredReader;
+
+    // An error after some data has been read is reported.
+    let mut reader = LayeredReader::new(FailAfter {
+        data: b"abc",
+        error: io::ErrorKind::Other,
+    });
+    let (mut a, mut b) = ([0; 3], [0; 3]);
+    let mut bufs = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)];
+    assert_eq!(
+        default_read_vectored_with_status(&mut reader, &mut bufs)
+            .unwrap_err()
+            .kind(),
+        io::ErrorKind::Other
+    );
+
+    // A request to retry returns the data read so far instead.
+    let mut reader = LayeredReader::new(FailAfter {
+        data: b"abc",
+        error: io::ErrorKind::WouldBlock,
+    });
+    let mut bufs = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)];
+    assert_eq!(
+        default_read_vectored_with_status(&mut reader, &mut bufs).unwrap(),
+        (3, Status::active())
+    );
+    assert_eq!(&a, b"abc");
+    reader.abandon();
+}
+
+#[test]
+fn test_read_vectored_eager_with_status_error() {
+    use crate::LayeredReader;
+
+    // An error after some data has been read is reported.
+    let mut reader = LayeredReader::new(FailAfter {
+        data: b"abcd",
+        error: io::ErrorKind::Other,
+    });
+    let mut a = [0; 8];
+    let mut bufs = [IoSliceMut::new(&mut a)];
+    assert_eq!(
+        reader
+            .read_vectored_eager_with_status(&mut bufs)
+            .unwrap_err()
+            .kind(),
+        io::ErrorKind::Other
+    );
+
+    // A request to retry returns the data read so far instead.
+    let mut reader = LayeredReader::new(FailAfter {
+        data: b"abcd",
+        error: io::ErrorKind::WouldBlock,
+    });
+    let mut bufs = [IoSliceMut::new(&mut a)];
+    assert_eq!(
+        reader.read_vectored_eager_with_status(&mut bufs).unwrap(),
+        (4, Status::active())
+    );
+    assert_eq!(&a[..4], b"abcd");
+    reader.abandon();
+}
+
+#[test]
+fn test_default_read_vectored_with_status() {
+    use crate::SliceReader;
+
+    let mut reader = SliceReader::new(b"hello world!");
+    let mut a = [0; 3];
+    let mut b = [0; 4];
+    let mut c = [0; 2];
+    let mut bufs = [
+        IoSliceMut::new(&mut a),
+        IoSliceMut::new(&mut []),
+        IoSliceMut::new(&mut b),
+        IoSliceMut::new(&mut c),
+    ];
+    assert_eq!(
+        default_read_vectored_with_status(&mut reader, &mut bufs).unwrap(),
+        (9, Status::active())
+    );
+    assert_eq!(&a, b"hel");
+    assert_eq!(&b, b"lo w");
+    assert_eq!(&c, b"or");
+
+    // A short read stops the filling, and the status is reported.
+    let mut a = [0; 2];
+    let mut b = [0; 4];
+    let mut c = [0; 2];
+    let mut bufs = [
+        IoSliceMut::new(&mut a),
+        IoSliceMut::new(&mut b),
+        IoSliceMut::new(&mut c),
+    ];
+    assert_eq!(
+        default_read_vectored_with_status(&mut reader, &mut bufs).unwrap(),
+        (3, Status::End)
+    );
+    assert_eq!(&a, b"ld");
+    assert_eq!(&b[..1], b"!");
+}
+
+#[test]
+fn test_read_to_string_invalid_utf8() {
+    use crate::LayeredReader;
+
+    let mut reader = LayeredReader::new(&b"hello \xff world"[..]);
+    let mut s = String::new();
+    assert_eq!(
+        reader.read_to_string(&mut s).unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+    assert!(s.is_empty());
+}
+
+#[test]
+fn test_empty_read_layered() {
+    let mut reader = io::empty();
+    let mut buf = [0; 4];
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (0, Status::End));
+    assert_eq!(
+        reader
+            .read_vectored_with_status(&mut [IoSliceMut::new(&mut buf)])
+            .unwrap(),
+        (0, Status::End)
+    );
+    assert_eq!(reader.suggested_buffer_size(), 0);
+    let mut v = Vec::new();
+    assert_eq!(default_read_to_end(&mut reader, &mut v).unwrap(), 0);
+    reader.abandon();
+}
+
+#[test]
+fn test_read_to_end_with_status() {
+    use crate::{LayeredReader, PacketReader};
+
+    let mut reader = LayeredReader::with_eos_as_push(io::Cursor::new(b"hello"));
+    let mut buf = Vec::new();
+    assert_eq!(
+        reader.read_to_end_with_status(&mut buf).unwrap(),
+        (5, Status::push())
+    );
+    assert_eq!(buf, b"hello");
+    reader.abandon();
+
+    let mut reader = LayeredReader::new(io::Cursor::new(b"hello"));
+    let mut buf = Vec::new();
+    assert_eq!(
+        reader.read_to_end_with_status(&mut buf).unwrap(),
+        (5, Status::End)
+    );
+
+    let packets = vec![b"ab".to_vec(), b"cd".to_vec()];
+    let mut reader = PacketReader::new(packets);
+    let mut buf = Vec::new();
+    assert_eq!(
+        reader.read_to_end_with_status(&mut buf).unwrap(),
+        (2, Status::push())
+    );
+    assert_eq!(
+        reader.read_to_end_with_status(&mut buf).unwrap(),
+        (2, Status::End)
+    );
+    assert_eq!(buf, b"abcd");
+}
+
+#[test]
+fn test_peek_unsupported() {
+    use crate::LayeredReader;
+
+    let mut reader = LayeredReader::new(io::Cursor::new(b"hello"));
+    let mut buf = [0; 2];
+    assert_eq!(
+        reader.peek(&mut buf).unwrap_err().kind(),
+        io::ErrorKind::Unsupported
+    );
+    reader.abandon();
+}
+
+#[test]
+fn test_read_to_end_with_status_unbuffered() {
+    use crate::SliceReader;
+
+    // `SliceReader` suggests a buffer size of 0.
+    let mut reader = SliceReader::new(b"hello world");
+    let mut v = Vec::new();
+    assert_eq!(
+        reader.read_to_end_with_status(&mut v).unwrap(),
+        (11, Status::End)
+    );
+    assert_eq!(v, b"hello world");
+}
+
+#[test]
+fn test_take_layered_borrowed() {
+    use crate::SliceReader;
+
+    let mut reader = SliceReader::new(b"hello world");
+    let mut v = Vec::new();
+    let status = (&mut reader)
+        .take_layered(5)
+        .read_to_end_with_status(&mut v)
+        .unwrap();
+    assert_eq!(v, b"hello");
+    assert_eq!(status, (5, Status::End));
+
+    // The rest of the stream can still be read.
+    v.clear();
+    let status = reader.read_to_end_with_status(&mut v).unwrap();
+    assert_eq!(v, b" world");
+    assert_eq!(status, (6, Status::End));
+}
+
+#[test]
+fn test_repeat_read_layered() {
+    let mut reader = io::repeat(b'x');
+    let mut buf = [0; 4];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (4, Status::active())
+    );
+    assert_eq!(&buf, b"xxxx");
+
+    let mut v = Vec::new();
+    let result = reader.take_layered(6).read_to_end_with_status(&mut v);
+    assert_eq!(result.unwrap(), (6, Status::End));
+    assert_eq!(v, b"xxxxxx");
+}
+
+#[test]
+fn test_read_exact_vectored_using_status() {
+    use crate::{IntervalPushReader, SliceReader};
+
+    let mut reader = SliceReader::new(b"hello world");
+    let (mut a, mut b) = ([0; 5], [0; 6]);
+    let mut bufs = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)];
+    let status = reader.read_exact_vectored_using_status(&mut bufs).unwrap();
+    assert_eq!(status, Status::End);
+    assert_eq!((&a, &b), (b"hello", b" world"));
+
+    // Short reads partially fill the buffers, and the status of the last
+    // read is reported.
+    let mut reader = IntervalPushReader::new(SliceReader::new(b"hello world"), 3);
+    let (mut a, mut b) = ([0; 5], [0; 4]);
+    let mut bufs = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)];
+    let status = reader.read_exact_vectored_using_status(&mut bufs).unwrap();
+    assert_eq!(status, Status::push());
+    assert_eq!((&a, &b), (b"hello", b" wor"));
+
+    let (mut a, mut b) = ([0; 1], [0; 0]);
+    let mut bufs = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)];
+    let status = reader.read_exact_vectored_using_status(&mut bufs).unwrap();
+    assert_eq!(status, Status::active());
+    assert_eq!(&a, b"l");
+
+    let mut reader = SliceReader::new(b"hello");
+    let (mut a, mut b) = ([0; 3], [0; 3]);
+    let mut bufs = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)];
+    assert_eq!(
+        reader
+            .read_exact_vectored_using_status(&mut bufs)
+            .unwrap_err()
+            .kind(),
+        io::ErrorKind::UnexpectedEof
+    );
+}
@@ -0,0 +1,155 @@
+use crate::{Bufferable, Status, WriteLayered};
+use std::fmt;
+use std::io::{self, Write};
+
+/// An operation on a [`WriteLayered`], as recorded by [`RecordWriter`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum WriteOp {
+    /// A successful [`Write::write`] of the given bytes.
+    Write(Vec<u8>),
+    /// A call to [`Write::flush`].
+    Flush,
+    /// A call to [`WriteLayered::flush_with_status`] with the given status.
+    FlushStatus(Status),
+    /// A call to [`WriteLayered::close`].
+    Close,
+}
+
+/// Adapts a [`WriteLayered`] to record each operation performed on it, so
+/// that they can be replayed later with [`replay`].
+///
+/// Writes are recorded with the bytes the inner stream accepted. Other
+/// operations are recorded before they're forwarded, whether or not they
+/// succeed.
+pub struct RecordWriter<Inner> {
+    inner: Inner,
+    ops: Vec<WriteOp>,
+}
+
+impl<Inner: WriteLayered> RecordWriter<Inner> {
+    /// Construct a new `RecordWriter` which wraps `inner`.
+    pub fn new(inner: Inner) -> Self {
+        Self {
+            inner,
+            ops: Vec::new(),
+        }
+    }
+
+    /// Return the operations recorded so far.
+    pub fn ops(&self) -> &[WriteOp] {
+        &self.ops
+    }
+
+    /// Take the operations recorded so far, leaving the record empty.
+    pub fn take_ops(&mut self) -> Vec<WriteOp> {
+        std::mem::take(&mut self.ops)
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Operations performed directly on the underlying writer aren't
+    /// recorded.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `RecordWriter` and return the inner stream and the
+    /// recorded operations.
+    pub fn into_inner(self) -> (Inner, Vec<WriteOp>) {
+        (self.inner, self.ops)
+    }
+}
+
+impl<Inner: WriteLayered> WriteLayered for RecordWriter<Inner> {
+    #[inline]
+    fn close(&mut self) -> io::Result<()> {
+        self.ops.push(WriteOp::Close);
+        self.inner.close()
+    }
+
+    #[inline]
+    fn flush_with_status(&mut self, status: Status) -> io::Result<()> {
+        self.ops.push(WriteOp::FlushStatus(status));
+        self.inner.flush_with_status(status)
+    }
+}
+
+impl<Inner: WriteLayered> Bufferable for RecordWriter<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: WriteLayered> Write for RecordWriter<Inner> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let size = self.inner.write(buf)?;
+        self.ops.push(WriteOp::Write(buf[..size].to_vec()));
+        Ok(size)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.ops.push(WriteOp::Flush);
+        self.inner.flush()
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for RecordWriter<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("RecordWriter");
+        b.field("inner", &self.inner);
+        b.field("ops", &self.ops.len());
+        b.finish()
+    }
+}
+
+/// Perform the operations in `ops` on `writer`, in order.
+pub fn replay(ops: &[WriteOp], writer: &mut dyn WriteLayered) -> io::Result<()> {
+    for op in ops {
+        match op {
+            WriteOp::Write(bytes) => writer.write_all(bytes)?,
+            WriteOp::Flush => writer.flush()?,
+            WriteOp::FlushStatus(status) => writer.flush_with_status(*status)?,
+            WriteOp::Close => writer.close()?,
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_record_writer_replay() {
+    let mut writer = RecordWriter::new(io::Cursor::new(Vec::new()));
+    writer.write_all(b"hello").unwrap();
+    writer.flush().unwrap();
+    writer.write_all(b" world").unwrap();
+    writer.flush_with_status(Status::push()).unwrap();
+    writer.close().unwrap();
+    let (first, ops) = writer.into_inner();
+    assert_eq!(
+        ops,
+        [
+            WriteOp::Write(b"hello".to_vec()),
+            WriteOp::Flush,
+            WriteOp::Write(b" world".to_vec()),
+            WriteOp::FlushStatus(Status::push()),
+            WriteOp::Close,
+        ]
+    );
+
+    let mut second = RecordWriter::new(io::Cursor::new(Vec::new()));
+    replay(&ops, &mut second).unwrap();
+    assert_eq!(second.ops(), &ops[..]);
+    assert_eq!(second.get_ref().get_ref(), first.get_ref());
+}
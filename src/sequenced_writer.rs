@@ -0,0 +1,181 @@
+use crate::{Bufferable, WriteLayered};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{self, Write};
+
+/// Adapts a [`WriteLayered`] to accept writes tagged with sequence numbers,
+/// forwarding them to the inner stream in sequence order.
+///
+/// Sequence numbers start at 0. A write with the next expected sequence
+/// number is forwarded immediately, along with any held writes which follow
+/// it. A write which arrives early, within a window of the next expected
+/// sequence number, is held until its turn. A write which is a duplicate, is
+/// late, or is beyond the window fails with `io::ErrorKind::InvalidInput`.
+///
+/// Plain [`Write::write`] calls are given the next expected sequence number.
+pub struct SequencedWriter<Inner> {
+    inner: Inner,
+    window: u64,
+    next_seq: u64,
+    pending: BTreeMap<u64, Vec<u8>>,
+}
+
+impl<Inner: WriteLayered> SequencedWriter<Inner> {
+    /// Construct a new `SequencedWriter` which wraps `inner` and holds
+    /// writes which arrive up to `window` sequence numbers early.
+    pub fn new(inner: Inner, window: u64) -> Self {
+        Self {
+            inner,
+            window,
+            next_seq: 0,
+            pending: BTreeMap::new(),
+        }
+    }
+
+    /// Write `buf` as the write with sequence number `seq`.
+    pub fn write_seq(&mut self, seq: u64, buf: &[u8]) -> io::Result<()> {
+        if seq < self.next_seq || self.pending.contains_key(&seq) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("sequence number {} was already written", seq),
+            ));
+        }
+        if seq - self.next_seq > self.window {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "sequence number {} is too far ahead of {}",
+                    seq, self.next_seq
+                ),
+            ));
+        }
+        if seq != self.next_seq {
+            self.pending.insert(seq, buf.to_vec());
+            return Ok(());
+        }
+
+        self.inner.write_all(buf)?;
+        self.next_seq += 1;
+        while let Some(held) = self.pending.remove(&self.next_seq) {
+            if let Err(e) = self.inner.write_all(&held) {
+                self.pending.insert(self.next_seq, held);
+                return Err(e);
+            }
+            self.next_seq += 1;
+        }
+        Ok(())
+    }
+
+    /// Return the sequence number of the next write to be forwarded.
+    pub fn next_seq(&self) -> u64 {
+        self.next_seq
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `SequencedWriter` and return the inner stream.
+    ///
+    /// Any held writes are discarded.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+}
+
+impl<Inner: WriteLayered> WriteLayered for SequencedWriter<Inner> {
+    fn close(&mut self) -> io::Result<()> {
+        if !self.pending.is_empty() {
+            self.pending.clear();
+            self.inner.abandon();
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("sequence number {} was never written", self.next_seq),
+            ));
+        }
+        self.inner.close()
+    }
+}
+
+impl<Inner: WriteLayered> Bufferable for SequencedWriter<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.pending.clear();
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: WriteLayered> Write for SequencedWriter<Inner> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_seq(self.next_seq, buf)?;
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for SequencedWriter<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("SequencedWriter");
+        b.field("inner", &self.inner);
+        b.field("window", &self.window);
+        b.field("next_seq", &self.next_seq);
+        b.field("pending", &self.pending.keys().collect::<Vec<_>>());
+        b.finish()
+    }
+}
+
+#[test]
+fn test_sequenced_writer_reorders() {
+    let mut writer = SequencedWriter::new(io::Cursor::new(Vec::new()), 4);
+    writer.write_seq(2, b"c").unwrap();
+    writer.write_seq(1, b"b").unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"");
+    writer.write_seq(0, b"a").unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"abc");
+    writer.write_seq(4, b"e").unwrap();
+    writer.write_seq(3, b"d").unwrap();
+    assert_eq!(writer.next_seq(), 5);
+    writer.close().unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"abcde");
+}
+
+#[test]
+fn test_sequenced_writer_errors() {
+    let mut writer = SequencedWriter::new(io::Cursor::new(Vec::new()), 2);
+    writer.write_seq(0, b"a").unwrap();
+    assert_eq!(
+        writer.write_seq(0, b"a").unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+    assert_eq!(
+        writer.write_seq(4, b"e").unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+    writer.write_seq(2, b"c").unwrap();
+    assert_eq!(
+        writer.write_seq(2, b"c").unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+    assert_eq!(
+        writer.close().unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+}
@@ -0,0 +1,136 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    ReadLayered, Status,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Adapts a [`ReadLayered`] to discard a fixed number of leading bytes.
+pub struct SkipReader<Inner> {
+    inner: Inner,
+    remaining: u64,
+}
+
+impl<Inner: ReadLayered> SkipReader<Inner> {
+    /// Construct a new `SkipReader` which wraps `inner` and discards the
+    /// first `skip` bytes read from it.
+    pub fn new(inner: Inner, skip: u64) -> Self {
+        Self {
+            inner,
+            remaining: skip,
+        }
+    }
+
+    /// Return the number of bytes which remain to be skipped.
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Bytes read directly from the underlying reader aren't counted as
+    /// skipped.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `SkipReader` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+}
+
+impl<Inner: ReadLayered> ReadLayered for SkipReader<Inner> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        while self.remaining != 0 {
+            if buf.is_empty() {
+                return Ok((0, Status::active()));
+            }
+            let (size, status) = self.inner.read_with_status(buf)?;
+            if (size as u64) > self.remaining {
+                // The skip ends partway through this read; shift the rest of
+                // the data down to the start of `buf`.
+                let skipped = self.remaining as usize;
+                self.remaining = 0;
+                buf.copy_within(skipped..size, 0);
+                return Ok((size - skipped, status));
+            }
+            self.remaining -= size as u64;
+            if size == 0 || status.is_end() {
+                return Ok((0, status));
+            }
+        }
+
+        self.inner.read_with_status(buf)
+    }
+
+    #[inline]
+    fn minimum_buffer_size(&self) -> usize {
+        self.inner.minimum_buffer_size()
+    }
+}
+
+impl<Inner: ReadLayered> Bufferable for SkipReader<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon()
+    }
+}
+
+impl<Inner: ReadLayered> Read for SkipReader<Inner> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for SkipReader<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("SkipReader");
+        b.field("inner", &self.inner);
+        b.field("remaining", &self.remaining);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_skip_reader() {
+    let mut reader = SkipReader::new(crate::SliceReader::new(b"header body"), 6);
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, " body");
+}
+
+#[test]
+fn test_skip_reader_spanning_reads() {
+    let mut reader = SkipReader::new(crate::SliceReader::new(b"header body"), 7);
+    let mut buf = [0; 3];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (2, Status::active())
+    );
+    assert_eq!(reader.remaining(), 0);
+    assert_eq!(&buf[..2], b"bo");
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (2, Status::End));
+    assert_eq!(&buf[..2], b"dy");
+}
@@ -0,0 +1,108 @@
+use crate::{Bufferable, WriteLayered};
+use std::fmt;
+use std::io::{self, Write};
+
+/// Adapts a [`WriteLayered`] to discard a fixed number of leading bytes.
+pub struct SkipWriter<Inner> {
+    inner: Inner,
+    remaining: u64,
+}
+
+impl<Inner: WriteLayered> SkipWriter<Inner> {
+    /// Construct a new `SkipWriter` which wraps `inner` and discards the
+    /// first `skip` bytes written to it.
+    pub fn new(inner: Inner, skip: u64) -> Self {
+        Self {
+            inner,
+            remaining: skip,
+        }
+    }
+
+    /// Return the number of bytes which remain to be skipped.
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `SkipWriter` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+}
+
+impl<Inner: WriteLayered> WriteLayered for SkipWriter<Inner> {
+    #[inline]
+    fn close(&mut self) -> io::Result<()> {
+        self.inner.close()
+    }
+}
+
+impl<Inner: WriteLayered> Bufferable for SkipWriter<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: WriteLayered> Write for SkipWriter<Inner> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.remaining == 0 {
+            return self.inner.write(buf);
+        }
+        let skip = self.remaining.min(buf.len() as u64) as usize;
+        self.remaining -= skip as u64;
+        if skip == buf.len() {
+            return Ok(skip);
+        }
+        Ok(skip + self.inner.write(&buf[skip..])?)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for SkipWriter<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("SkipWriter");
+        b.field("inner", &self.inner);
+        b.field("remaining", &self.remaining);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_skip_writer() {
+    let mut writer = SkipWriter::new(io::Cursor::new(Vec::new()), 2);
+    writer.write_all(b"XXbody").unwrap();
+    writer.close().unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"body");
+}
+
+#[test]
+fn test_skip_writer_spanning_writes() {
+    let mut writer = SkipWriter::new(io::Cursor::new(Vec::new()), 5);
+    writer.write_all(b"XX").unwrap();
+    writer.write_all(b"XX").unwrap();
+    writer.write_all(b"Xbo").unwrap();
+    writer.write_all(b"dy").unwrap();
+    writer.close().unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"body");
+}
@@ -1,31 +1,65 @@
 use crate::{Bufferable, ReadLayered, Status};
-use std::io::{self, IoSliceMut, Read};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read, Seek, SeekFrom};
+use std::mem::MaybeUninit;
 
 /// Adapts an `&[u8]` to implement [`ReadLayered`].
+///
+/// This also implements [`Seek`]. Reads report `Status::End` when they reach
+/// the end of the slice, and seeking back before the end makes reads report
+/// `Status::active()` again.
+#[derive(Clone)]
 pub struct SliceReader<'slice> {
     slice: &'slice [u8],
+    pos: usize,
 }
 
 impl<'slice> SliceReader<'slice> {
     /// Construct a new `SliceReader` which wraps `slice`.
     #[inline]
     pub fn new(slice: &'slice [u8]) -> Self {
-        Self { slice }
+        Self { slice, pos: 0 }
+    }
+
+    /// Return the current position within the slice.
+    ///
+    /// This may be past the end of the slice, if it was seeked there.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Return the part of the slice which hasn't been read yet.
+    #[inline]
+    pub fn remaining(&self) -> &'slice [u8] {
+        &self.slice[self.pos.min(self.slice.len())..]
+    }
+
+    /// Consume this `SliceReader` and return the part of the slice which
+    /// hasn't been read yet.
+    #[inline]
+    pub fn into_inner(self) -> &'slice [u8] {
+        self.remaining()
+    }
+
+    /// Advance past `size` bytes which have been read, and return the status
+    /// to report with them.
+    #[inline]
+    fn advance(&mut self, size: usize) -> Status {
+        self.pos += size;
+        if self.remaining().is_empty() {
+            Status::End
+        } else {
+            Status::active()
+        }
     }
 }
 
 impl<'slice> ReadLayered for SliceReader<'slice> {
     #[inline]
     fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
-        let size = Read::read(&mut self.slice, buf)?;
-        Ok((
-            size,
-            if self.slice.is_empty() {
-                Status::End
-            } else {
-                Status::active()
-            },
-        ))
+        let size = Read::read(&mut self.remaining(), buf)?;
+        Ok((size, self.advance(size)))
     }
 
     #[inline]
@@ -33,22 +67,45 @@ impl<'slice> ReadLayered for SliceReader<'slice> {
         &mut self,
         bufs: &mut [IoSliceMut<'_>],
     ) -> io::Result<(usize, Status)> {
-        let size = Read::read_vectored(&mut self.slice, bufs)?;
+        let size = Read::read_vectored(&mut self.remaining(), bufs)?;
+        Ok((size, self.advance(size)))
+    }
+
+    #[inline]
+    fn peek(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        let remaining = self.remaining();
+        let size = buf.len().min(remaining.len());
+        buf[..size].copy_from_slice(&remaining[..size]);
         Ok((
             size,
-            if self.slice.is_empty() {
+            if size == remaining.len() {
                 Status::End
             } else {
                 Status::active()
             },
         ))
     }
+
+    /// Copies directly from the slice, without zeroing `buf` first.
+    #[inline]
+    fn read_uninit_with_status(
+        &mut self,
+        buf: &mut [MaybeUninit<u8>],
+    ) -> io::Result<(usize, Status)> {
+        let remaining = self.remaining();
+        let size = buf.len().min(remaining.len());
+        for (dst, src) in buf.iter_mut().zip(&remaining[..size]) {
+            *dst = MaybeUninit::new(*src);
+        }
+        Ok((size, self.advance(size)))
+    }
 }
 
 impl<'slice> Bufferable for SliceReader<'slice> {
     #[inline]
     fn abandon(&mut self) {
         self.slice = &[];
+        self.pos = 0;
     }
 
     #[inline]
@@ -61,33 +118,95 @@ impl<'slice> Bufferable for SliceReader<'slice> {
 impl<'slice> Read for SliceReader<'slice> {
     #[inline]
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        Read::read(&mut self.slice, buf)
+        let size = Read::read(&mut self.remaining(), buf)?;
+        self.pos += size;
+        Ok(size)
     }
 
     #[inline]
     fn read_vectored(&mut self, bufs: &mut [io::IoSliceMut<'_>]) -> io::Result<usize> {
-        Read::read_vectored(&mut self.slice, bufs)
+        let size = Read::read_vectored(&mut self.remaining(), bufs)?;
+        self.pos += size;
+        Ok(size)
     }
 
     #[cfg(can_vector)]
     #[inline]
     fn is_read_vectored(&self) -> bool {
-        Read::is_read_vectored(&self.slice)
+        Read::is_read_vectored(&self.remaining())
     }
 
     #[inline]
     fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
-        Read::read_to_end(&mut self.slice, buf)
+        let size = Read::read_to_end(&mut self.remaining(), buf)?;
+        self.pos += size;
+        Ok(size)
     }
 
     #[inline]
     fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
-        Read::read_to_string(&mut self.slice, buf)
+        let mut remaining = self.remaining();
+        let result = Read::read_to_string(&mut remaining, buf);
+        self.pos = self.slice.len() - remaining.len();
+        result
     }
 
     #[inline]
     fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
-        Read::read_exact(&mut self.slice, buf)
+        let mut remaining = self.remaining();
+        let result = Read::read_exact(&mut remaining, buf);
+        self.pos = self.slice.len() - remaining.len();
+        result
+    }
+}
+
+impl<'slice> Seek for SliceReader<'slice> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(offset) => (0, offset as i128),
+            SeekFrom::End(offset) => (self.slice.len(), offset as i128),
+            SeekFrom::Current(offset) => (self.pos, offset as i128),
+        };
+        let new_pos = base as i128 + offset;
+        if new_pos < 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative position",
+            ));
+        }
+        if new_pos > usize::MAX as i128 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to an overflowing position",
+            ));
+        }
+        self.pos = new_pos as usize;
+        Ok(self.pos as u64)
+    }
+}
+
+/// The number of upcoming bytes shown by `SliceReader`'s `Debug` output.
+const DEBUG_PREVIEW_LEN: usize = 16;
+
+/// Shows the length of the remaining data and a hex preview of its start,
+/// rather than the whole slice.
+impl<'slice> fmt::Debug for SliceReader<'slice> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let remaining = self.remaining();
+        let mut next = remaining
+            .iter()
+            .take(DEBUG_PREVIEW_LEN)
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<Vec<_>>()
+            .join(" ");
+        if remaining.len() > DEBUG_PREVIEW_LEN {
+            next.push_str(" ..");
+        }
+        let mut b = f.debug_struct("SliceReader");
+        b.field("position", &self.pos);
+        b.field("remaining", &remaining.len());
+        b.field("next", &format_args!("[{}]", next));
+        b.finish()
     }
 }
 
@@ -122,3 +241,134 @@ fn test_slice_read() {
     assert_eq!(&buf[..2], b"d!");
     assert_eq!(reader.read(&mut buf).unwrap(), 0);
 }
+
+#[test]
+fn test_slice_read_uninit_with_status() {
+    let mut reader = SliceReader::new(b"hello world!");
+    let mut buf = [MaybeUninit::<u8>::uninit(); 5];
+    assert_eq!(
+        reader.read_uninit_with_status(&mut buf).unwrap(),
+        (5, Status::active())
+    );
+    assert_eq!(reader.remaining(), b" world!");
+    assert_eq!(
+        reader.read_uninit_with_status(&mut buf).unwrap(),
+        (5, Status::active())
+    );
+    assert_eq!(
+        reader.read_uninit_with_status(&mut buf).unwrap(),
+        (2, Status::End)
+    );
+    assert_eq!(reader.position(), 12);
+    assert_eq!(
+        reader.read_uninit_with_status(&mut buf).unwrap(),
+        (0, Status::End)
+    );
+}
+
+#[test]
+fn test_default_read_uninit_with_status() {
+    use crate::{default_read_uninit_with_status, LayeredReader};
+
+    let mut reader = LayeredReader::new(io::Cursor::new(b"hello"));
+    let mut buf = [MaybeUninit::<u8>::uninit(); 8];
+    assert_eq!(
+        default_read_uninit_with_status(&mut reader, &mut buf).unwrap(),
+        (5, Status::active())
+    );
+    assert_eq!(reader.get_ref().position(), 5);
+
+    // Reads into a large buffer are limited to the stack buffer.
+    let mut reader = LayeredReader::new(io::Cursor::new(vec![0; 0x3000]));
+    let mut buf = vec![MaybeUninit::<u8>::uninit(); 0x3000];
+    assert_eq!(
+        default_read_uninit_with_status(&mut reader, &mut buf).unwrap(),
+        (0x2000, Status::active())
+    );
+}
+
+#[test]
+fn test_slice_reader_seek() {
+    let mut reader = SliceReader::new(b"hello world!");
+    let mut buf = vec![0; 16];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (12, Status::End)
+    );
+    assert_eq!(reader.position(), 12);
+    assert!(reader.remaining().is_empty());
+
+    // Seeking backward makes the stream active again.
+    assert_eq!(reader.seek(SeekFrom::End(-6)).unwrap(), 6);
+    assert_eq!(reader.remaining(), b"world!");
+    assert_eq!(
+        reader.read_with_status(&mut buf[..2]).unwrap(),
+        (2, Status::active())
+    );
+    assert_eq!(&buf[..2], b"wo");
+    assert_eq!(reader.seek(SeekFrom::Current(-8)).unwrap(), 0);
+    assert_eq!(
+        reader.read_with_status(&mut buf[..5]).unwrap(),
+        (5, Status::active())
+    );
+    assert_eq!(&buf[..5], b"hello");
+    assert_eq!(reader.seek(SeekFrom::Start(9)).unwrap(), 9);
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (3, Status::End));
+    assert_eq!(&buf[..3], b"ld!");
+
+    // Seeking past the end is allowed, and reads nothing.
+    assert_eq!(reader.seek(SeekFrom::Start(20)).unwrap(), 20);
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (0, Status::End));
+    assert_eq!(
+        reader.seek(SeekFrom::Current(-21)).unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+    assert_eq!(reader.position(), 20);
+}
+
+#[test]
+fn test_slice_reader_peek() {
+    let mut reader = SliceReader::new(b"\x7fELF...");
+    let mut magic = [0; 4];
+    assert_eq!(reader.peek(&mut magic).unwrap(), (4, Status::active()));
+    assert_eq!(&magic, b"\x7fELF");
+    let mut buf = [0; 16];
+    assert_eq!(reader.peek(&mut buf).unwrap(), (7, Status::End));
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (7, Status::End));
+    assert_eq!(&buf[..7], b"\x7fELF...");
+}
+
+#[test]
+fn test_slice_reader_into_inner() {
+    let mut reader = SliceReader::new(b"key=value");
+    let mut key = [0; 4];
+    reader.read_exact_using_status(&mut key).unwrap();
+    assert_eq!(&key, b"key=");
+    assert_eq!(reader.position(), 4);
+    assert_eq!(reader.remaining(), b"value");
+    assert_eq!(reader.into_inner(), b"value");
+}
+
+#[test]
+fn test_slice_reader_clone_debug() {
+    let mut reader = SliceReader::new(b"0123456789abcdefghij");
+    let mut buf = [0; 2];
+    reader.read_exact_using_status(&mut buf).unwrap();
+    assert_eq!(
+        format!("{:?}", reader),
+        "SliceReader { position: 2, remaining: 18, next: \
+         [32 33 34 35 36 37 38 39 61 62 63 64 65 66 67 68 ..] }"
+    );
+
+    // A clone reads independently of the original.
+    let mut clone = reader.clone();
+    let mut s = String::new();
+    clone.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "23456789abcdefghij");
+    assert_eq!(reader.remaining().len(), 18);
+
+    assert_eq!(
+        format!("{:?}", clone),
+        "SliceReader { position: 20, remaining: 0, next: [] }"
+    );
+}
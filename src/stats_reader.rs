@@ -0,0 +1,169 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Activity,
+    Bufferable, ReadLayered, Status,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Statistics about the reads performed on a [`StatsReader`].
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default)]
+pub struct ReadStats {
+    /// The number of successful reads.
+    pub reads: u64,
+    /// The total number of bytes read.
+    pub bytes: u64,
+    /// The size of the smallest read, or 0 if there have been no reads.
+    pub min_read: usize,
+    /// The size of the largest read.
+    pub max_read: usize,
+    /// The number of reads which returned `Status::active()`.
+    pub active_reads: u64,
+    /// The number of reads which returned `Status::push()`.
+    pub push_reads: u64,
+    /// The number of reads which returned `Status::End`.
+    pub end_reads: u64,
+}
+
+impl ReadStats {
+    /// Return the mean size of the reads, or `None` if there have been no
+    /// reads.
+    pub fn mean_read(&self) -> Option<f64> {
+        if self.reads == 0 {
+            None
+        } else {
+            Some(self.bytes as f64 / self.reads as f64)
+        }
+    }
+}
+
+/// Adapts a [`ReadLayered`] to collect [`ReadStats`] about the reads
+/// performed on it.
+///
+/// This is the reading counterpart of [`StatsWriter`], and can help evaluate
+/// how a source chunks its data and how often it pushes. Data and statuses
+/// are returned unchanged.
+///
+/// [`StatsWriter`]: crate::StatsWriter
+pub struct StatsReader<Inner> {
+    inner: Inner,
+    stats: ReadStats,
+}
+
+impl<Inner: ReadLayered> StatsReader<Inner> {
+    /// Construct a new `StatsReader` which wraps `inner`.
+    pub fn new(inner: Inner) -> Self {
+        Self {
+            inner,
+            stats: ReadStats::default(),
+        }
+    }
+
+    /// Return a snapshot of the statistics collected so far.
+    pub fn stats(&self) -> ReadStats {
+        self.stats
+    }
+
+    /// Consume this `StatsReader` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+}
+
+impl<Inner: ReadLayered> ReadLayered for StatsReader<Inner> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        let (size, status) = self.inner.read_with_status(buf)?;
+        let stats = &mut self.stats;
+        stats.min_read = if stats.reads == 0 {
+            size
+        } else {
+            stats.min_read.min(size)
+        };
+        stats.max_read = stats.max_read.max(size);
+        stats.reads += 1;
+        stats.bytes += size as u64;
+        match status {
+            Status::Open(Activity::Active) => stats.active_reads += 1,
+            Status::Open(Activity::Push) => stats.push_reads += 1,
+            Status::End => stats.end_reads += 1,
+        }
+        Ok((size, status))
+    }
+
+    #[inline]
+    fn minimum_buffer_size(&self) -> usize {
+        self.inner.minimum_buffer_size()
+    }
+}
+
+impl<Inner: ReadLayered> Bufferable for StatsReader<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon()
+    }
+}
+
+impl<Inner: ReadLayered> Read for StatsReader<Inner> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for StatsReader<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("StatsReader");
+        b.field("inner", &self.inner);
+        b.field("stats", &self.stats);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_stats_reader() {
+    use crate::PacketReader;
+
+    let packets = vec![b"abcdefgh".to_vec(), b"ij".to_vec(), b"klmn".to_vec()];
+    let mut reader = StatsReader::new(PacketReader::new(packets));
+    assert_eq!(reader.stats().mean_read(), None);
+
+    let mut buf = [0; 5];
+    let mut total = 0;
+    loop {
+        let (size, status) = reader.read_with_status(&mut buf).unwrap();
+        total += size;
+        if status.is_end() {
+            break;
+        }
+    }
+    assert_eq!(total, 14);
+
+    let stats = reader.stats();
+    assert_eq!(
+        stats,
+        ReadStats {
+            reads: 4,
+            bytes: 14,
+            min_read: 2,
+            max_read: 5,
+            active_reads: 1,
+            push_reads: 2,
+            end_reads: 1,
+        }
+    );
+    assert_eq!(stats.mean_read(), Some(3.5));
+}
@@ -0,0 +1,170 @@
+use crate::{Activity, Bufferable, Status, WriteLayered};
+use std::fmt;
+use std::io::{self, Write};
+
+/// Statistics about the operations performed on a [`StatsWriter`].
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default)]
+pub struct WriteStats {
+    /// The number of successful [`Write::write`] calls.
+    pub writes: u64,
+    /// The total number of bytes written.
+    pub bytes: u64,
+    /// The size of the smallest write, or 0 if there have been no writes.
+    pub min_write: usize,
+    /// The size of the largest write.
+    pub max_write: usize,
+    /// The number of calls to [`WriteLayered::flush_with_status`] with
+    /// `Status::active()`.
+    pub active_flushes: u64,
+    /// The number of calls to [`WriteLayered::flush_with_status`] with
+    /// `Status::push()`.
+    pub push_flushes: u64,
+    /// The number of calls to [`WriteLayered::flush_with_status`] with
+    /// `Status::End`.
+    pub end_flushes: u64,
+}
+
+impl WriteStats {
+    /// Return the mean size of the writes, or `None` if there have been no
+    /// writes.
+    pub fn mean_write(&self) -> Option<f64> {
+        if self.writes == 0 {
+            None
+        } else {
+            Some(self.bytes as f64 / self.writes as f64)
+        }
+    }
+}
+
+/// Adapts a [`WriteLayered`] to collect [`WriteStats`] about the operations
+/// performed on it.
+///
+/// This can help diagnose whether a buffering layer is effective, for
+/// example by checking that it turns many small writes into a few large
+/// ones. Writes are counted with the number of bytes the inner stream
+/// accepted. Everything is forwarded to the inner stream unchanged.
+pub struct StatsWriter<Inner> {
+    inner: Inner,
+    stats: WriteStats,
+}
+
+impl<Inner: WriteLayered> StatsWriter<Inner> {
+    /// Construct a new `StatsWriter` which wraps `inner`.
+    pub fn new(inner: Inner) -> Self {
+        Self {
+            inner,
+            stats: WriteStats::default(),
+        }
+    }
+
+    /// Return a snapshot of the statistics collected so far.
+    pub fn stats(&self) -> WriteStats {
+        self.stats
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Operations performed directly on the underlying writer aren't
+    /// counted.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `StatsWriter` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+}
+
+impl<Inner: WriteLayered> WriteLayered for StatsWriter<Inner> {
+    #[inline]
+    fn close(&mut self) -> io::Result<()> {
+        self.inner.close()
+    }
+
+    #[inline]
+    fn flush_with_status(&mut self, status: Status) -> io::Result<()> {
+        match status {
+            Status::Open(Activity::Active) => self.stats.active_flushes += 1,
+            Status::Open(Activity::Push) => self.stats.push_flushes += 1,
+            Status::End => self.stats.end_flushes += 1,
+        }
+        self.inner.flush_with_status(status)
+    }
+}
+
+impl<Inner: WriteLayered> Bufferable for StatsWriter<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: WriteLayered> Write for StatsWriter<Inner> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let size = self.inner.write(buf)?;
+        let stats = &mut self.stats;
+        stats.min_write = if stats.writes == 0 {
+            size
+        } else {
+            stats.min_write.min(size)
+        };
+        stats.max_write = stats.max_write.max(size);
+        stats.writes += 1;
+        stats.bytes += size as u64;
+        Ok(size)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for StatsWriter<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("StatsWriter");
+        b.field("inner", &self.inner);
+        b.field("stats", &self.stats);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_stats_writer() {
+    let mut writer = StatsWriter::new(io::Cursor::new(Vec::new()));
+    assert_eq!(writer.stats(), WriteStats::default());
+    assert_eq!(writer.stats().mean_write(), None);
+
+    writer.write_all(b"ab").unwrap();
+    writer.write_all(&[0; 10]).unwrap();
+    writer.flush_with_status(Status::active()).unwrap();
+    writer.write_all(b"cdef").unwrap();
+    writer.flush_with_status(Status::push()).unwrap();
+    writer.flush_with_status(Status::End).unwrap();
+
+    let stats = writer.stats();
+    assert_eq!(
+        stats,
+        WriteStats {
+            writes: 3,
+            bytes: 16,
+            min_write: 2,
+            max_write: 10,
+            active_flushes: 1,
+            push_flushes: 1,
+            end_flushes: 1,
+        }
+    );
+    assert_eq!(stats.mean_write(), Some(16.0 / 3.0));
+}
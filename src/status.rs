@@ -1,5 +1,9 @@
 /// What is known about a stream in the future.
+///
+/// With the `serde` feature, this implements `Serialize` and `Deserialize`,
+/// in the form `{"Open":"Active"}`, `{"Open":"Push"}`, or `"End"` in JSON.
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Status {
     /// The stream is open.
     Open(Activity),
@@ -32,6 +36,48 @@ impl Status {
     pub fn is_push(self) -> bool {
         self == Self::Open(Activity::Push)
     }
+
+    /// Shorthand for testing equality with `Status::Open(Activity::Active)`.
+    #[inline]
+    pub fn is_active(self) -> bool {
+        self == Self::Open(Activity::Active)
+    }
+
+    /// Return the activity state of an open stream, or `None` if the stream
+    /// has ended.
+    #[inline]
+    pub fn activity(self) -> Option<Activity> {
+        match self {
+            Self::Open(activity) => Some(activity),
+            Self::End => None,
+        }
+    }
+
+    /// Combine the statuses of two streams into one, for adapters which read
+    /// from or write to several streams.
+    ///
+    /// The result is `End` only if both are `End`. Otherwise, it's `Push` if
+    /// either is `Push`, and `Active` if not:
+    ///
+    /// | `self`   | `other`  | result   |
+    /// |----------|----------|----------|
+    /// | `End`    | `End`    | `End`    |
+    /// | `End`    | `Push`   | `Push`   |
+    /// | `End`    | `Active` | `Active` |
+    /// | `Push`   | any      | `Push`   |
+    /// | `Active` | `Push`   | `Push`   |
+    /// | `Active` | `Active` | `Active` |
+    /// | `Active` | `End`    | `Active` |
+    ///
+    /// This is commutative and associative, and `End` is its identity.
+    #[inline]
+    pub fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::End, Self::End) => Self::End,
+            (Self::Open(Activity::Push), _) | (_, Self::Open(Activity::Push)) => Self::push(),
+            _ => Self::active(),
+        }
+    }
 }
 
 /// For interactivity, it's desirable to avoid buffering data which is complete
@@ -41,6 +87,7 @@ impl Status {
 ///
 /// Users that aren't implementing buffering can ignore this.
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Activity {
     /// The writer is actively writing and data may be buffered.
     Active,
@@ -53,3 +100,63 @@ pub enum Activity {
     /// [`PSH` flag]: https://en.wikipedia.org/wiki/Transmission_Control_Protocol#TCP_segment_structure
     Push,
 }
+
+impl Activity {
+    /// Shorthand for testing equality with `Activity::Active`.
+    #[inline]
+    pub fn is_active(self) -> bool {
+        self == Self::Active
+    }
+
+    /// Shorthand for testing equality with `Activity::Push`.
+    #[inline]
+    pub fn is_push(self) -> bool {
+        self == Self::Push
+    }
+}
+
+#[test]
+fn test_status_predicates() {
+    assert!(Status::active().is_active());
+    assert!(!Status::push().is_active());
+    assert!(!Status::End.is_active());
+    assert_eq!(Status::active().activity(), Some(Activity::Active));
+    assert_eq!(Status::push().activity(), Some(Activity::Push));
+    assert_eq!(Status::End.activity(), None);
+    assert!(Activity::Active.is_active());
+    assert!(!Activity::Active.is_push());
+    assert!(Activity::Push.is_push());
+    assert!(!Activity::Push.is_active());
+}
+
+#[test]
+fn test_status_merge() {
+    let all = [Status::active(), Status::push(), Status::End];
+    for a in all {
+        assert_eq!(a.merge(Status::End), a);
+        for b in all {
+            assert_eq!(a.merge(b), b.merge(a));
+            for c in all {
+                assert_eq!(a.merge(b).merge(c), a.merge(b.merge(c)));
+            }
+        }
+    }
+    assert_eq!(Status::End.merge(Status::End), Status::End);
+    assert_eq!(Status::active().merge(Status::push()), Status::push());
+    assert_eq!(Status::active().merge(Status::active()), Status::active());
+    assert_eq!(Status::End.merge(Status::push()), Status::push());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_status_serde() {
+    for (status, json) in [
+        (Status::active(), r#"{"Open":"Active"}"#),
+        (Status::push(), r#"{"Open":"Push"}"#),
+        (Status::End, r#""End""#),
+    ] {
+        assert_eq!(serde_json::to_string(&status).unwrap(), json);
+        assert_eq!(serde_json::from_str::<Status>(json).unwrap(), status);
+    }
+    assert_eq!(serde_json::to_string(&Activity::Push).unwrap(), r#""Push""#);
+}
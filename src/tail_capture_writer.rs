@@ -0,0 +1,117 @@
+use crate::{Bufferable, WriteLayered};
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, Write};
+
+/// Adapts a [`WriteLayered`] to retain a copy of the most recent bytes
+/// written to it.
+///
+/// All writes are forwarded to the inner stream. Up to a fixed number of the
+/// most recently written bytes are kept, and can be retrieved with
+/// [`TailCaptureWriter::tail`], for example to attach recent output to an
+/// error report.
+pub struct TailCaptureWriter<Inner> {
+    inner: Inner,
+    capacity: usize,
+    tail: VecDeque<u8>,
+}
+
+impl<Inner: WriteLayered> TailCaptureWriter<Inner> {
+    /// Construct a new `TailCaptureWriter` which wraps `inner` and retains
+    /// the last `capacity` bytes written.
+    pub fn new(inner: Inner, capacity: usize) -> Self {
+        Self {
+            inner,
+            capacity,
+            tail: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Return a copy of the last bytes written, up to the capacity.
+    pub fn tail(&self) -> Vec<u8> {
+        self.tail.iter().copied().collect()
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Bytes written directly to the underlying writer aren't captured.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `TailCaptureWriter` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+
+    fn capture(&mut self, data: &[u8]) {
+        let data = &data[data.len().saturating_sub(self.capacity)..];
+        let excess = (self.tail.len() + data.len()).saturating_sub(self.capacity);
+        self.tail.drain(..excess);
+        self.tail.extend(data);
+    }
+}
+
+impl<Inner: WriteLayered> WriteLayered for TailCaptureWriter<Inner> {
+    #[inline]
+    fn close(&mut self) -> io::Result<()> {
+        self.inner.close()
+    }
+}
+
+impl<Inner: WriteLayered> Bufferable for TailCaptureWriter<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: WriteLayered> Write for TailCaptureWriter<Inner> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let size = self.inner.write(buf)?;
+        self.capture(&buf[..size]);
+        Ok(size)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for TailCaptureWriter<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("TailCaptureWriter");
+        b.field("inner", &self.inner);
+        b.field("capacity", &self.capacity);
+        b.field("captured", &self.tail.len());
+        b.finish()
+    }
+}
+
+#[test]
+fn test_tail_capture_writer() {
+    let mut writer = TailCaptureWriter::new(io::Cursor::new(Vec::new()), 8);
+    writer.write_all(b"hello").unwrap();
+    assert_eq!(writer.tail(), b"hello");
+    writer.write_all(b" world").unwrap();
+    assert_eq!(writer.tail(), b"lo world");
+    writer.write_all(b", and everyone else").unwrap();
+    assert_eq!(writer.tail(), b"one else");
+    writer.close().unwrap();
+    assert_eq!(
+        writer.get_ref().get_ref(),
+        b"hello world, and everyone else"
+    );
+}
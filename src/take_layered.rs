@@ -0,0 +1,157 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    ReadLayered, Status,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// Adapts a [`ReadLayered`] to read at most a limited number of bytes.
+///
+/// This is like [`Read::take`], but it preserves statuses. The read which
+/// reaches the limit reports `Status::End`, and after that, reads return
+/// `(0, Status::End)` without reading from the inner stream, so the rest of
+/// the inner stream can still be read with [`TakeLayered::into_inner`].
+pub struct TakeLayered<Inner> {
+    inner: Inner,
+    limit: u64,
+}
+
+impl<Inner: ReadLayered> TakeLayered<Inner> {
+    /// Construct a new `TakeLayered` which reads at most `limit` bytes from
+    /// `inner`.
+    pub fn new(inner: Inner, limit: u64) -> Self {
+        Self { inner, limit }
+    }
+
+    /// Return the number of bytes which can still be read before the limit
+    /// is reached.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Set the number of bytes which can be read before the limit is
+    /// reached.
+    pub fn set_limit(&mut self, limit: u64) {
+        self.limit = limit;
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Bytes read directly from the underlying reader don't count toward the
+    /// limit.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `TakeLayered` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+}
+
+impl<Inner: ReadLayered> ReadLayered for TakeLayered<Inner> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        if self.limit == 0 {
+            return Ok((0, Status::End));
+        }
+        let max = buf.len().min(self.limit.min(usize::MAX as u64) as usize);
+        let (size, status) = self.inner.read_with_status(&mut buf[..max])?;
+        self.limit -= size as u64;
+        if status.is_end() {
+            self.limit = 0;
+        }
+        Ok((size, if self.limit == 0 { Status::End } else { status }))
+    }
+
+    #[inline]
+    fn minimum_buffer_size(&self) -> usize {
+        self.inner
+            .minimum_buffer_size()
+            .min(self.limit.min(usize::MAX as u64) as usize)
+    }
+}
+
+impl<Inner: ReadLayered> Bufferable for TakeLayered<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.limit = 0;
+        self.inner.abandon()
+    }
+}
+
+impl<Inner: ReadLayered> Read for TakeLayered<Inner> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for TakeLayered<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("TakeLayered");
+        b.field("inner", &self.inner);
+        b.field("limit", &self.limit);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_take_layered() {
+    use crate::SliceReader;
+
+    let mut reader = TakeLayered::new(SliceReader::new(b"hello world!"), 5);
+    let mut buf = [0; 3];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (3, Status::active())
+    );
+    assert_eq!(reader.limit(), 2);
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (2, Status::End));
+    assert_eq!(&buf[..2], b"lo");
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (0, Status::End));
+
+    let mut inner = reader.into_inner();
+    let mut rest = Vec::new();
+    inner.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, b" world!");
+}
+
+#[test]
+fn test_take_layered_short() {
+    use crate::PacketReader;
+
+    let packets = vec![b"ab".to_vec(), b"cd".to_vec()];
+    let mut reader = TakeLayered::new(PacketReader::new(packets), 10);
+    let mut buf = [0; 8];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (2, Status::push())
+    );
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (2, Status::End));
+    assert_eq!(reader.limit(), 0);
+
+    reader.set_limit(4);
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "");
+}
@@ -0,0 +1,160 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored,
+    default_suggested_buffer_size, Bufferable, ReadLayered, Status,
+};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read, Write};
+
+/// Adapts a [`ReadLayered`] to write a copy of everything read from it to a
+/// [`Write`], for example to log or checksum a stream.
+///
+/// Each chunk is written in full to the writer before the read returns it,
+/// with the status from the inner stream. If writing fails, the read fails
+/// with the write's error. The writer is a plain [`Write`], so abandoning
+/// this stream abandons the inner stream but leaves the writer as it is.
+pub struct TeeReader<Inner, W> {
+    inner: Inner,
+    writer: W,
+}
+
+impl<Inner: ReadLayered, W: Write> TeeReader<Inner, W> {
+    /// Construct a new `TeeReader` which reads from `inner` and writes a
+    /// copy of the data to `writer`.
+    pub fn new(inner: Inner, writer: W) -> Self {
+        Self { inner, writer }
+    }
+
+    /// Gets a reference to the writer.
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+
+    /// Gets a mutable reference to the writer.
+    pub fn writer_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Consume this `TeeReader` and return the inner stream and the writer.
+    pub fn into_inner(self) -> (Inner, W) {
+        (self.inner, self.writer)
+    }
+}
+
+impl<Inner: ReadLayered, W: Write> ReadLayered for TeeReader<Inner, W> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        let (size, status) = self.inner.read_with_status(buf)?;
+        self.writer.write_all(&buf[..size])?;
+        Ok((size, status))
+    }
+
+    #[inline]
+    fn minimum_buffer_size(&self) -> usize {
+        self.inner.minimum_buffer_size()
+    }
+}
+
+impl<Inner: ReadLayered, W> Bufferable for TeeReader<Inner, W> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        // Some streams, such as `SliceReader`, suggest 0, which
+        // `default_read_to_end` can't make progress with.
+        match self.inner.suggested_buffer_size() {
+            0 => default_suggested_buffer_size(self),
+            size => size,
+        }
+    }
+}
+
+impl<Inner: ReadLayered, W: Write> Read for TeeReader<Inner, W> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl<Inner: fmt::Debug, W: fmt::Debug> fmt::Debug for TeeReader<Inner, W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("TeeReader");
+        b.field("inner", &self.inner);
+        b.field("writer", &self.writer);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_tee_reader() {
+    use crate::PacketReader;
+
+    let packets = vec![b"hello ".to_vec(), b"world".to_vec()];
+    let mut reader = TeeReader::new(PacketReader::new(packets), Vec::new());
+    let mut buf = [0; 4];
+    let mut read = Vec::new();
+    let mut statuses = Vec::new();
+    loop {
+        let (size, status) = reader.read_with_status(&mut buf).unwrap();
+        read.extend_from_slice(&buf[..size]);
+        statuses.push(status);
+        if status.is_end() {
+            break;
+        }
+    }
+    assert_eq!(read, b"hello world");
+    assert!(statuses.contains(&Status::push()));
+    assert_eq!(reader.writer(), b"hello world");
+}
+
+#[test]
+fn test_tee_reader_read_to_end() {
+    use crate::SliceReader;
+
+    let mut reader = TeeReader::new(SliceReader::new(b"hello world"), Vec::new());
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, b"hello world");
+    let (_, writer) = reader.into_inner();
+    assert_eq!(writer, b"hello world");
+}
+
+#[test]
+fn test_tee_reader_write_error() {
+    use crate::SliceReader;
+
+    struct FailingWriter;
+
+    impl Write for FailingWriter {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::Other, "tee failed"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut reader = TeeReader::new(SliceReader::new(b"hello"), FailingWriter);
+    let mut buf = [0; 5];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap_err().kind(),
+        io::ErrorKind::Other
+    );
+}
@@ -0,0 +1,173 @@
+use crate::{Bufferable, Clock, WriteLayered};
+use std::fmt;
+use std::io::{self, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Adapts a [`WriteLayered`] to limit the rate at which data is written to
+/// it, such as to simulate a slow link.
+///
+/// After each write, if the data written since the first write has been
+/// written faster than the limit allows, the write sleeps until the
+/// average rate is back within the limit. Time spent between writes counts
+/// towards the average, so a writer which has been idle may write a burst
+/// without sleeping. Flushes and closes are passed through without
+/// throttling.
+pub struct ThrottledWriter<Inner, C, S> {
+    inner: Inner,
+    clock: C,
+    sleep: S,
+    bytes_per_second: u64,
+    start: Option<Instant>,
+    written: u64,
+}
+
+impl<Inner: WriteLayered> ThrottledWriter<Inner, fn() -> Instant, fn(Duration)> {
+    /// Construct a new `ThrottledWriter` which wraps `inner` and writes at
+    /// most `bytes_per_second` bytes per second on average, using
+    /// [`Instant::now`] and [`thread::sleep`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes_per_second` is zero.
+    pub fn new(inner: Inner, bytes_per_second: u64) -> Self {
+        Self::with_clock_and_sleep(inner, bytes_per_second, Instant::now, thread::sleep)
+    }
+}
+
+impl<Inner: WriteLayered, C: Clock, S: FnMut(Duration)> ThrottledWriter<Inner, C, S> {
+    /// Construct a new `ThrottledWriter` which wraps `inner` and writes at
+    /// most `bytes_per_second` bytes per second on average, measuring time
+    /// with `clock` and waiting with `sleep`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes_per_second` is zero.
+    pub fn with_clock_and_sleep(inner: Inner, bytes_per_second: u64, clock: C, sleep: S) -> Self {
+        assert_ne!(bytes_per_second, 0, "rate limit must be non-zero");
+        Self {
+            inner,
+            clock,
+            sleep,
+            bytes_per_second,
+            start: None,
+            written: 0,
+        }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Bytes written directly to the underlying writer aren't throttled.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `ThrottledWriter` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+
+    /// Sleep until the bytes written since `start` are within the limit.
+    fn throttle(&mut self, start: Instant) {
+        let nanos = u128::from(self.written) * 1_000_000_000 / u128::from(self.bytes_per_second);
+        let target = Duration::from_nanos(nanos.try_into().unwrap_or(u64::MAX));
+        let elapsed = self
+            .clock
+            .now()
+            .checked_duration_since(start)
+            .unwrap_or_default();
+        if let Some(delay) = target.checked_sub(elapsed) {
+            if delay != Duration::ZERO {
+                (self.sleep)(delay);
+            }
+        }
+    }
+}
+
+impl<Inner: WriteLayered, C: Clock, S: FnMut(Duration)> WriteLayered
+    for ThrottledWriter<Inner, C, S>
+{
+    #[inline]
+    fn close(&mut self) -> io::Result<()> {
+        self.inner.close()
+    }
+}
+
+impl<Inner: WriteLayered, C, S> Bufferable for ThrottledWriter<Inner, C, S> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: WriteLayered, C: Clock, S: FnMut(Duration)> Write for ThrottledWriter<Inner, C, S> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let start = match self.start {
+            Some(start) => start,
+            None => *self.start.insert(self.clock.now()),
+        };
+        let size = self.inner.write(buf)?;
+        self.written += size as u64;
+        self.throttle(start);
+        Ok(size)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<Inner: fmt::Debug, C, S> fmt::Debug for ThrottledWriter<Inner, C, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("ThrottledWriter");
+        b.field("inner", &self.inner);
+        b.field("bytes_per_second", &self.bytes_per_second);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_throttled_writer() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    // A mock clock, which advances only when sleeping or when told to.
+    let time = Rc::new(Cell::new(Instant::now()));
+    let start = time.get();
+    let clock = {
+        let time = Rc::clone(&time);
+        move || time.get()
+    };
+    let sleep = {
+        let time = Rc::clone(&time);
+        move |d| time.set(time.get() + d)
+    };
+
+    let inner = io::Cursor::new(Vec::new());
+    let mut writer = ThrottledWriter::with_clock_and_sleep(inner, 100, clock, sleep);
+    for chunk in [0_u8; 250].chunks(10) {
+        writer.write_all(chunk).unwrap();
+    }
+    writer.flush().unwrap();
+    assert_eq!(writer.get_ref().get_ref().len(), 250);
+    assert!(time.get() - start >= Duration::from_millis(2500));
+
+    // Time spent between writes counts towards the limit.
+    time.set(time.get() + Duration::from_secs(10));
+    let before = time.get();
+    writer.write_all(&[0_u8; 500]).unwrap();
+    assert_eq!(time.get(), before);
+    writer.write_all(&[0_u8; 600]).unwrap();
+    assert_eq!(time.get() - start, Duration::from_millis(13500));
+}
@@ -0,0 +1,193 @@
+use crate::{Bufferable, WriteLayered};
+use std::fmt;
+use std::io::{self, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Adapts a [`WriteLayered`] to prefix each line written to it with an
+/// ISO-8601 timestamp.
+///
+/// Timestamps are in UTC with millisecond precision, such as
+/// `2021-03-04T05:06:07.089Z`, and are followed by a space. As with
+/// [`IndentWriter`], the prefix is written when the first byte of a line is
+/// written, so the timestamp records when the line was started. The clock is
+/// a function, so that it can be replaced for testing.
+///
+/// [`IndentWriter`]: crate::IndentWriter
+pub struct TimestampWriter<Inner, F> {
+    inner: Inner,
+    clock: F,
+    at_line_start: bool,
+}
+
+impl<Inner: WriteLayered> TimestampWriter<Inner, fn() -> SystemTime> {
+    /// Construct a new `TimestampWriter` which wraps `inner` and uses
+    /// [`SystemTime::now`] as its clock.
+    pub fn new(inner: Inner) -> Self {
+        Self::with_clock(inner, SystemTime::now)
+    }
+}
+
+impl<Inner: WriteLayered, F: FnMut() -> SystemTime> TimestampWriter<Inner, F> {
+    /// Construct a new `TimestampWriter` which wraps `inner` and calls
+    /// `clock` to obtain the time at the start of each line.
+    pub fn with_clock(inner: Inner, clock: F) -> Self {
+        Self {
+            inner,
+            clock,
+            at_line_start: true,
+        }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `TimestampWriter` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+
+    fn write_timestamp(&mut self) -> io::Result<()> {
+        let (secs, millis) = match (self.clock)().duration_since(UNIX_EPOCH) {
+            Ok(d) => (d.as_secs() as i64, d.subsec_millis()),
+            Err(e) => {
+                let d = e.duration();
+                match d.subsec_millis() {
+                    0 => (-(d.as_secs() as i64), 0),
+                    millis => (-(d.as_secs() as i64) - 1, 1000 - millis),
+                }
+            }
+        };
+        let (year, month, day) = civil_from_days(secs.div_euclid(86400));
+        let secs_of_day = secs.rem_euclid(86400);
+        write!(
+            self.inner,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z ",
+            year,
+            month,
+            day,
+            secs_of_day / 3600,
+            secs_of_day / 60 % 60,
+            secs_of_day % 60,
+            millis
+        )
+    }
+}
+
+/// Convert a number of days since 1970-01-01 into a year, month, and day in
+/// the proleptic Gregorian calendar.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+impl<Inner: WriteLayered, F: FnMut() -> SystemTime> WriteLayered for TimestampWriter<Inner, F> {
+    #[inline]
+    fn close(&mut self) -> io::Result<()> {
+        self.inner.close()
+    }
+}
+
+impl<Inner: WriteLayered, F> Bufferable for TimestampWriter<Inner, F> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: WriteLayered, F: FnMut() -> SystemTime> Write for TimestampWriter<Inner, F> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut rest = buf;
+        while !rest.is_empty() {
+            if self.at_line_start {
+                self.write_timestamp()?;
+                self.at_line_start = false;
+            }
+            let line_len = match rest.iter().position(|b| *b == b'\n') {
+                Some(newline) => {
+                    self.at_line_start = true;
+                    newline + 1
+                }
+                None => rest.len(),
+            };
+            self.inner.write_all(&rest[..line_len])?;
+            rest = &rest[line_len..];
+        }
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<Inner: fmt::Debug, F> fmt::Debug for TimestampWriter<Inner, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("TimestampWriter");
+        b.field("inner", &self.inner);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_timestamp_writer() {
+    use std::time::Duration;
+
+    let mut now = UNIX_EPOCH + Duration::from_millis(1_614_834_367_089);
+    let clock = move || {
+        let t = now;
+        now += Duration::from_secs(1);
+        t
+    };
+    let mut writer = TimestampWriter::with_clock(io::Cursor::new(Vec::new()), clock);
+    writer.write_all(b"first\nsec").unwrap();
+    writer.write_all(b"ond\n").unwrap();
+    writer.close().unwrap();
+    assert_eq!(
+        String::from_utf8_lossy(writer.get_ref().get_ref()),
+        "2021-03-04T05:06:07.089Z first\n2021-03-04T05:06:08.089Z second\n"
+    );
+}
+
+#[test]
+fn test_timestamp_writer_dates() {
+    use std::time::Duration;
+
+    let format = |t: SystemTime| {
+        let mut writer = TimestampWriter::with_clock(io::Cursor::new(Vec::new()), || t);
+        writer.write_all(b"x").unwrap();
+        writer.close().unwrap();
+        String::from_utf8(writer.into_inner().into_inner()).unwrap()
+    };
+    assert_eq!(format(UNIX_EPOCH), "1970-01-01T00:00:00.000Z x");
+    assert_eq!(
+        format(UNIX_EPOCH + Duration::from_secs(951_782_400)),
+        "2000-02-29T00:00:00.000Z x"
+    );
+    assert_eq!(
+        format(UNIX_EPOCH - Duration::from_millis(1)),
+        "1969-12-31T23:59:59.999Z x"
+    );
+}
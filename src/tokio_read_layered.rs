@@ -23,7 +23,7 @@ pub trait TokioReadLayered: AsyncRead + Bufferable {
 }
 
 /// Default implementation of [`AsyncRead::poll_read`] in terms of
-/// [`AsyncReadLayered::poll_read_with_status`].
+/// [`TokioReadLayered::poll_read_with_status`].
 #[inline]
 pub fn tokio_default_poll_read<Inner: TokioReadLayered + ?Sized>(
     inner: Pin<&mut Inner>,
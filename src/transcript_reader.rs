@@ -0,0 +1,140 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    ReadLayered, Status,
+};
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, IoSliceMut, Read};
+
+/// A [`ReadLayered`] which replays a transcript of reads, for reproducing a
+/// stream deterministically.
+///
+/// The transcript is a list of chunks, each with the status that was read
+/// with it, such as one captured by recording the results of
+/// `read_with_status` on another stream. Each read returns data from at most
+/// one chunk, with the chunk's status once its end is reached; if the buffer
+/// is too small for the rest of a chunk, the read reports
+/// `Status::active()`. After the last chunk, `Status::End` is reported.
+///
+/// This is the reading counterpart of [`RecordWriter`].
+///
+/// [`RecordWriter`]: crate::RecordWriter
+pub struct TranscriptReader {
+    chunks: VecDeque<(Vec<u8>, Status)>,
+    pos: usize,
+}
+
+impl TranscriptReader {
+    /// Construct a new `TranscriptReader` which replays `chunks` in order.
+    pub fn new(chunks: Vec<(Vec<u8>, Status)>) -> Self {
+        Self {
+            chunks: chunks.into(),
+            pos: 0,
+        }
+    }
+}
+
+impl ReadLayered for TranscriptReader {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        let (chunk, status) = match self.chunks.front() {
+            Some(front) => front,
+            None => return Ok((0, Status::End)),
+        };
+        let size = (chunk.len() - self.pos).min(buf.len());
+        buf[..size].copy_from_slice(&chunk[self.pos..self.pos + size]);
+        self.pos += size;
+        if self.pos < chunk.len() {
+            return Ok((size, Status::active()));
+        }
+        let status = *status;
+        self.chunks.pop_front();
+        self.pos = 0;
+        if status.is_end() {
+            self.chunks.clear();
+        }
+        Ok((size, status))
+    }
+}
+
+impl Bufferable for TranscriptReader {
+    #[inline]
+    fn abandon(&mut self) {
+        self.chunks.clear();
+        self.pos = 0;
+    }
+}
+
+impl Read for TranscriptReader {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+impl fmt::Debug for TranscriptReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("TranscriptReader");
+        b.field("chunks", &self.chunks.len());
+        b.field("pos", &self.pos);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_transcript_reader_round_trip() {
+    use crate::{IntervalPushReader, SliceReader};
+
+    // Capture a transcript of a stream.
+    let mut original = IntervalPushReader::new(SliceReader::new(b"hello world"), 4);
+    let mut transcript = Vec::new();
+    let mut buf = [0; 3];
+    loop {
+        let (size, status) = original.read_with_status(&mut buf).unwrap();
+        transcript.push((buf[..size].to_vec(), status));
+        if status.is_end() {
+            break;
+        }
+    }
+
+    // Replaying it with the same buffer size reproduces every read.
+    let mut replayed = Vec::new();
+    let mut reader = TranscriptReader::new(transcript.clone());
+    loop {
+        let (size, status) = reader.read_with_status(&mut buf).unwrap();
+        replayed.push((buf[..size].to_vec(), status));
+        if status.is_end() {
+            break;
+        }
+    }
+    assert_eq!(replayed, transcript);
+}
+
+#[test]
+fn test_transcript_reader_small_buffer() {
+    let mut reader = TranscriptReader::new(vec![(b"abc".to_vec(), Status::push())]);
+    let mut buf = [0; 2];
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (2, Status::active())
+    );
+    assert_eq!(
+        reader.read_with_status(&mut buf).unwrap(),
+        (1, Status::push())
+    );
+    assert_eq!(reader.read_with_status(&mut buf).unwrap(), (0, Status::End));
+}
@@ -0,0 +1,127 @@
+use crate::{Bufferable, WriteLayered};
+use std::fmt;
+use std::io::{self, Write};
+
+/// Writes to two [`WriteLayered`]s, transforming the data for each one with
+/// its own function.
+///
+/// For example, one branch can write the raw data to a file while the other
+/// writes an uppercased copy to a display. Each write is transformed and
+/// written in full to the first branch and then to the second. Flushes,
+/// pushes, and [`WriteLayered::close`] are forwarded to both branches.
+pub struct TransformTeeWriter<A, B, FA, FB> {
+    a: A,
+    b: B,
+    fa: FA,
+    fb: FB,
+}
+
+impl<A, B, FA, FB> TransformTeeWriter<A, B, FA, FB>
+where
+    A: WriteLayered,
+    B: WriteLayered,
+    FA: FnMut(&[u8]) -> Vec<u8>,
+    FB: FnMut(&[u8]) -> Vec<u8>,
+{
+    /// Construct a new `TransformTeeWriter` which writes data transformed by
+    /// `fa` to `a` and data transformed by `fb` to `b`.
+    pub fn new(a: A, fa: FA, b: B, fb: FB) -> Self {
+        Self { a, b, fa, fb }
+    }
+
+    /// Gets references to the underlying writers.
+    pub fn get_ref(&self) -> (&A, &B) {
+        (&self.a, &self.b)
+    }
+
+    /// Gets mutable references to the underlying writers.
+    ///
+    /// It is inadvisable to directly write to the underlying writers.
+    pub fn get_mut(&mut self) -> (&mut A, &mut B) {
+        (&mut self.a, &mut self.b)
+    }
+
+    /// Consume this `TransformTeeWriter` and return the inner streams.
+    pub fn into_inner(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+
+impl<A, B, FA, FB> WriteLayered for TransformTeeWriter<A, B, FA, FB>
+where
+    A: WriteLayered,
+    B: WriteLayered,
+    FA: FnMut(&[u8]) -> Vec<u8>,
+    FB: FnMut(&[u8]) -> Vec<u8>,
+{
+    /// Close both branches. The second branch is closed even if closing the
+    /// first fails, and the first error is returned.
+    fn close(&mut self) -> io::Result<()> {
+        let a = self.a.close();
+        let b = self.b.close();
+        a.and(b)
+    }
+}
+
+impl<A: WriteLayered, B: WriteLayered, FA, FB> Bufferable for TransformTeeWriter<A, B, FA, FB> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.a.abandon();
+        self.b.abandon();
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.a
+            .suggested_buffer_size()
+            .max(self.b.suggested_buffer_size())
+    }
+}
+
+impl<A, B, FA, FB> Write for TransformTeeWriter<A, B, FA, FB>
+where
+    A: WriteLayered,
+    B: WriteLayered,
+    FA: FnMut(&[u8]) -> Vec<u8>,
+    FB: FnMut(&[u8]) -> Vec<u8>,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.a.write_all(&(self.fa)(buf))?;
+        self.b.write_all(&(self.fb)(buf))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let a = self.a.flush();
+        let b = self.b.flush();
+        a.and(b)
+    }
+}
+
+impl<A: fmt::Debug, B: fmt::Debug, FA, FB> fmt::Debug for TransformTeeWriter<A, B, FA, FB> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("TransformTeeWriter");
+        b.field("a", &self.a);
+        b.field("b", &self.b);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_transform_tee_writer() {
+    use crate::Status;
+
+    let mut writer = TransformTeeWriter::new(
+        io::Cursor::new(Vec::new()),
+        |buf: &[u8]| buf.to_vec(),
+        io::Cursor::new(Vec::new()),
+        |buf: &[u8]| buf.to_ascii_uppercase(),
+    );
+    writer.write_all(b"hello, ").unwrap();
+    writer.flush_with_status(Status::push()).unwrap();
+    writer.write_all(b"world").unwrap();
+    writer.flush_with_status(Status::End).unwrap();
+    let (raw, display) = writer.get_ref();
+    assert_eq!(raw.get_ref(), b"hello, world");
+    assert_eq!(display.get_ref(), b"HELLO, WORLD");
+}
@@ -0,0 +1,135 @@
+use crate::{
+    default_read, default_read_to_end, default_read_to_string, default_read_vectored, Bufferable,
+    ReadLayered, Status,
+};
+use std::io::{self, IoSliceMut, Read};
+
+/// Adapts a [`ReadLayered`] to remove trailing spaces and tabs from each line.
+///
+/// A run of spaces and tabs is held back until the next byte is seen; if it's
+/// a `\n`, the run is dropped, and otherwise it's passed through. At the end
+/// of the stream, any held-back run is passed through.
+pub struct TrimTrailingReader<Inner> {
+    inner: Inner,
+    held: Vec<u8>,
+    ready: Vec<u8>,
+    ready_status: Status,
+}
+
+impl<Inner: ReadLayered> TrimTrailingReader<Inner> {
+    /// Construct a new `TrimTrailingReader` which wraps `inner`.
+    pub fn new(inner: Inner) -> Self {
+        Self {
+            inner,
+            held: Vec::new(),
+            ready: Vec::new(),
+            ready_status: Status::active(),
+        }
+    }
+
+    /// Consume this `TrimTrailingReader` and return the inner stream.
+    ///
+    /// Any held-back or not-yet-returned data is discarded.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+
+    fn take_ready(&mut self, buf: &mut [u8]) -> (usize, Status) {
+        let size = self.ready.len().min(buf.len());
+        buf[..size].copy_from_slice(&self.ready[..size]);
+        self.ready.drain(..size);
+        if self.ready.is_empty() {
+            (size, self.ready_status)
+        } else {
+            (size, Status::active())
+        }
+    }
+}
+
+impl<Inner: ReadLayered> ReadLayered for TrimTrailingReader<Inner> {
+    fn read_with_status(&mut self, buf: &mut [u8]) -> io::Result<(usize, Status)> {
+        if !self.ready.is_empty() || self.ready_status.is_end() {
+            return Ok(self.take_ready(buf));
+        }
+
+        let (size, status) = self.inner.read_with_status(buf)?;
+        for &byte in &buf[..size] {
+            match byte {
+                b' ' | b'\t' => self.held.push(byte),
+                b'\n' => {
+                    self.held.clear();
+                    self.ready.push(byte);
+                }
+                _ => {
+                    self.ready.append(&mut self.held);
+                    self.ready.push(byte);
+                }
+            }
+        }
+        if status.is_end() {
+            self.ready.append(&mut self.held);
+        }
+        self.ready_status = status;
+
+        Ok(self.take_ready(buf))
+    }
+
+    #[inline]
+    fn minimum_buffer_size(&self) -> usize {
+        self.inner.minimum_buffer_size()
+    }
+}
+
+impl<Inner: ReadLayered> Bufferable for TrimTrailingReader<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon();
+        self.held.clear();
+        self.ready.clear();
+    }
+}
+
+impl<Inner: ReadLayered> Read for TrimTrailingReader<Inner> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        default_read(self, buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        default_read_vectored(self, bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        default_read_to_end(self, buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        default_read_to_string(self, buf)
+    }
+}
+
+#[test]
+fn test_trim_trailing_reader() {
+    let mut reader = TrimTrailingReader::new(crate::SliceReader::new(b"a   \nb"));
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "a\nb");
+}
+
+#[test]
+fn test_trim_trailing_reader_split() {
+    let mut reader = TrimTrailingReader::new(crate::SliceReader::new(b"a \t \nb  c \t"));
+    let mut s = Vec::new();
+    let mut buf = [0; 2];
+    loop {
+        let (size, status) = reader.read_with_status(&mut buf).unwrap();
+        s.extend_from_slice(&buf[..size]);
+        if status.is_end() {
+            break;
+        }
+    }
+    assert_eq!(s, b"a\nb  c \t");
+}
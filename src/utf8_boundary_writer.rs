@@ -0,0 +1,203 @@
+use crate::{Bufferable, WriteLayered};
+use std::fmt;
+use std::io::{self, Write};
+
+/// Adapts a [`WriteLayered`] so that multi-byte UTF-8 sequences are never
+/// split across writes to the inner stream.
+///
+/// A trailing incomplete UTF-8 sequence is held back until the rest of it is
+/// written. Bytes which aren't valid UTF-8 are forwarded as-is; this only
+/// ensures that valid characters arrive whole. If a sequence is still
+/// incomplete on [`WriteLayered::close`], the close fails with
+/// `io::ErrorKind::InvalidData`.
+pub struct Utf8BoundaryWriter<Inner> {
+    inner: Inner,
+    held: Vec<u8>,
+}
+
+impl<Inner: WriteLayered> Utf8BoundaryWriter<Inner> {
+    /// Construct a new `Utf8BoundaryWriter` which wraps `inner`.
+    pub fn new(inner: Inner) -> Self {
+        Self {
+            inner,
+            held: Vec::new(),
+        }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `Utf8BoundaryWriter` and return the inner stream.
+    ///
+    /// Any held-back incomplete sequence is discarded.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+}
+
+/// Return the length of the UTF-8 sequence which begins with `byte`, or 1 if
+/// `byte` can't begin a multi-byte sequence.
+fn sequence_len(byte: u8) -> usize {
+    match byte {
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => 1,
+    }
+}
+
+/// Return the offset in `buf` of a trailing incomplete UTF-8 sequence, or
+/// `buf.len()` if there isn't one.
+fn incomplete_tail(buf: &[u8]) -> usize {
+    for back in 1..=buf.len().min(3) {
+        let i = buf.len() - back;
+        let byte = buf[i];
+        if byte & 0xc0 != 0x80 {
+            return if sequence_len(byte) > back {
+                i
+            } else {
+                buf.len()
+            };
+        }
+    }
+    buf.len()
+}
+
+impl<Inner: WriteLayered> WriteLayered for Utf8BoundaryWriter<Inner> {
+    fn close(&mut self) -> io::Result<()> {
+        if !self.held.is_empty() {
+            self.inner.abandon();
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "stream ended with an incomplete UTF-8 sequence",
+            ));
+        }
+        self.inner.close()
+    }
+}
+
+impl<Inner: WriteLayered> Bufferable for Utf8BoundaryWriter<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.held.clear();
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: WriteLayered> Write for Utf8BoundaryWriter<Inner> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut rest = buf;
+
+        // Complete a sequence held back from a previous write.
+        if !self.held.is_empty() {
+            let needed = sequence_len(self.held[0]) - self.held.len();
+            let take = needed.min(rest.len());
+            self.held.extend_from_slice(&rest[..take]);
+            rest = &rest[take..];
+            if take < needed {
+                return Ok(buf.len());
+            }
+            self.inner.write_all(&self.held)?;
+            self.held.clear();
+        }
+
+        let split = incomplete_tail(rest);
+        self.inner.write_all(&rest[..split])?;
+        self.held.extend_from_slice(&rest[split..]);
+        Ok(buf.len())
+    }
+
+    /// Flush the inner stream. A held-back incomplete sequence isn't
+    /// forwarded.
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for Utf8BoundaryWriter<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("Utf8BoundaryWriter");
+        b.field("inner", &self.inner);
+        b.field("held", &self.held);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_utf8_boundary_writer_split_char() {
+    let snowman = "☃".as_bytes();
+    let mut writer = Utf8BoundaryWriter::new(io::Cursor::new(Vec::new()));
+    writer.write_all(b"a").unwrap();
+    writer.write_all(&snowman[..1]).unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"a");
+    writer.write_all(&snowman[1..2]).unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"a");
+    writer.write_all(&[snowman[2], b'b']).unwrap();
+    assert_eq!(writer.get_ref().get_ref(), "a☃b".as_bytes());
+    writer.close().unwrap();
+}
+
+#[test]
+fn test_utf8_boundary_writer_chunks_are_complete() {
+    // Record each chunk the inner writer receives, and check that every one
+    // is valid UTF-8.
+    struct Chunks(Vec<Vec<u8>>);
+
+    impl WriteLayered for Chunks {
+        fn close(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Bufferable for Chunks {
+        fn abandon(&mut self) {}
+    }
+
+    impl Write for Chunks {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if !buf.is_empty() {
+                self.0.push(buf.to_vec());
+            }
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let text = "héllo wörld 😀!".as_bytes();
+    let mut writer = Utf8BoundaryWriter::new(Chunks(Vec::new()));
+    for chunk in text.chunks(3) {
+        writer.write_all(chunk).unwrap();
+    }
+    writer.close().unwrap();
+    let chunks = &writer.get_ref().0;
+    assert!(chunks.iter().all(|c| std::str::from_utf8(c).is_ok()));
+    assert_eq!(chunks.concat(), text);
+}
+
+#[test]
+fn test_utf8_boundary_writer_incomplete_at_close() {
+    let mut writer = Utf8BoundaryWriter::new(io::Cursor::new(Vec::new()));
+    writer.write_all(&"é".as_bytes()[..1]).unwrap();
+    assert_eq!(
+        writer.close().unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+}
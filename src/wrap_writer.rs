@@ -0,0 +1,135 @@
+use crate::{Bufferable, WriteLayered};
+use std::fmt;
+use std::io::{self, Write};
+
+/// Adapts a [`WriteLayered`] to wrap lines written to it at a column width.
+///
+/// A `\n` is inserted before any byte which would go past `width` columns,
+/// and the column is reset after each `\n`, whether inserted or written.
+/// Lines are wrapped at exactly `width` columns, not at word boundaries.
+///
+/// Columns are counted in bytes, so lines containing multi-byte UTF-8
+/// sequences are wrapped early, and may be split in the middle of a
+/// sequence. Wide characters and control characters such as tabs aren't
+/// accounted for either.
+pub struct WrapWriter<Inner> {
+    inner: Inner,
+    width: usize,
+    column: usize,
+}
+
+impl<Inner: WriteLayered> WrapWriter<Inner> {
+    /// Construct a new `WrapWriter` which wraps `inner` and wraps lines at
+    /// `width` columns.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is zero.
+    pub fn new(inner: Inner, width: usize) -> Self {
+        assert!(width != 0, "width must be non-zero");
+        Self {
+            inner,
+            width,
+            column: 0,
+        }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut Inner {
+        &mut self.inner
+    }
+
+    /// Consume this `WrapWriter` and return the inner stream.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+}
+
+impl<Inner: WriteLayered> WriteLayered for WrapWriter<Inner> {
+    #[inline]
+    fn close(&mut self) -> io::Result<()> {
+        self.inner.close()
+    }
+}
+
+impl<Inner: WriteLayered> Bufferable for WrapWriter<Inner> {
+    #[inline]
+    fn abandon(&mut self) {
+        self.inner.abandon()
+    }
+
+    #[inline]
+    fn suggested_buffer_size(&self) -> usize {
+        self.inner.suggested_buffer_size()
+    }
+}
+
+impl<Inner: WriteLayered> Write for WrapWriter<Inner> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut rest = buf;
+        while !rest.is_empty() {
+            if self.column == self.width && rest[0] != b'\n' {
+                self.inner.write_all(b"\n")?;
+                self.column = 0;
+            }
+            let room = (self.width - self.column).min(rest.len());
+            let len = match rest[..room].iter().position(|b| *b == b'\n') {
+                Some(newline) => newline + 1,
+                // Only an existing newline fits when the line is full.
+                None if room == 0 => 1,
+                None => room,
+            };
+            self.inner.write_all(&rest[..len])?;
+            if rest[len - 1] == b'\n' {
+                self.column = 0;
+            } else {
+                self.column += len;
+            }
+            rest = &rest[len..];
+        }
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<Inner: fmt::Debug> fmt::Debug for WrapWriter<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut b = f.debug_struct("WrapWriter");
+        b.field("inner", &self.inner);
+        b.field("width", &self.width);
+        b.finish()
+    }
+}
+
+#[test]
+fn test_wrap_writer() {
+    let mut writer = WrapWriter::new(io::Cursor::new(Vec::new()), 10);
+    writer.write_all(b"0123456789abcdefghij").unwrap();
+    writer.write_all(b"klm\nshort\n").unwrap();
+    writer.close().unwrap();
+    assert_eq!(
+        writer.get_ref().get_ref(),
+        b"0123456789\nabcdefghij\nklm\nshort\n"
+    );
+}
+
+#[test]
+fn test_wrap_writer_exact_width() {
+    // A line of exactly `width` bytes followed by its newline isn't wrapped.
+    let mut writer = WrapWriter::new(io::Cursor::new(Vec::new()), 4);
+    writer.write_all(b"abcd").unwrap();
+    writer.write_all(b"\nefghi").unwrap();
+    writer.close().unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"abcd\nefgh\ni");
+}
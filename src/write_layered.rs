@@ -25,6 +25,19 @@ pub trait WriteLayered: Write + Bufferable {
             Status::End => self.close(),
         }
     }
+
+    /// Like [`Write::write_all`], followed by [`flush_with_status`], so that
+    /// a complete message can be written and pushed, or written as the end
+    /// of the stream, in one call.
+    ///
+    /// [`flush_with_status`]: WriteLayered::flush_with_status
+    fn write_all_with_status(&mut self, buf: &[u8], status: Status) -> io::Result<()> {
+        self.write_all(buf)?;
+        match status {
+            Status::End => self.close(),
+            status => self.flush_with_status(status),
+        }
+    }
 }
 
 /// Default implementation of [`Write::write_vectored`], in terms of
@@ -42,7 +55,9 @@ pub fn default_write_vectored<Inner: Write + ?Sized>(
 
 /// Default implementation of [`Write::is_write_vectored`] accompanying
 /// [`default_write_vectored`].
-#[cfg(can_vector)]
+///
+/// `Write::is_write_vectored` itself is unstable, but this function is
+/// available on stable Rust.
 #[inline]
 pub fn default_is_write_vectored<Inner: Write + ?Sized>(_inner: &Inner) -> bool {
     false
@@ -73,58 +88,50 @@ pub fn default_write_all<Inner: Write + ?Sized>(
 
 /// Default implementation of [`Write::write_all_vectored`], in terms of
 /// [`Write::write_vectored`].
-#[cfg(write_all_vectored)]
+///
+/// `Write::write_all_vectored` itself is unstable, but this function is
+/// available on stable Rust, to write all of `bufs` to any [`Write`]. If a
+/// buffer is only partially written, the rest of it is written with
+/// [`Write::write_all`] before continuing with the following buffers.
+#[allow(clippy::indexing_slicing)]
 pub fn default_write_all_vectored<Inner: Write + ?Sized>(
     inner: &mut Inner,
-    mut bufs: &mut [IoSlice],
+    bufs: &mut [IoSlice<'_>],
 ) -> io::Result<()> {
-    // TODO: Use [rust-lang/rust#70436]once it stabilizes.
-    // [rust-lang/rust#70436]: https://github.com/rust-lang/rust/issues/70436
-    while !bufs.is_empty() {
-        match inner.write_vectored(bufs) {
-            Ok(nwritten) => bufs = advance(bufs, nwritten),
-            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => (),
-            Err(e) => return Err(e),
+    // The index of the first buffer not completely written, and the number
+    // of bytes of it which have been written.
+    let mut index = 0;
+    let mut offset = 0;
+    loop {
+        while let Some(buf) = bufs.get(index) {
+            if offset < buf.len() {
+                break;
+            }
+            offset -= buf.len();
+            index += 1;
         }
-    }
-    Ok(())
-}
-
-/// This will be obviated by [rust-lang/rust#62726].
-///
-/// [rust-lang/rust#62726]: https://github.com/rust-lang/rust/issues/62726.
-///
-/// Once this is removed, layered-io can become a `#![forbid(unsafe_code)]`
-/// crate.
-#[cfg(write_all_vectored)]
-fn advance<'a, 'b>(bufs: &'b mut [IoSlice<'a>], n: usize) -> &'b mut [IoSlice<'a>] {
-    use std::slice;
-
-    // Number of buffers to remove.
-    let mut remove = 0;
-    // Total length of all the to be removed buffers.
-    let mut accumulated_len = 0;
-    for buf in bufs.iter() {
-        if accumulated_len + buf.len() > n {
-            break;
+        let buf = match bufs.get(index) {
+            Some(buf) => buf,
+            None => return Ok(()),
+        };
+        if offset != 0 {
+            inner.write_all(&buf[offset..])?;
+            index += 1;
+            offset = 0;
+            continue;
         }
-        accumulated_len += buf.len();
-        remove += 1;
-    }
-
-    #[allow(clippy::indexing_slicing)]
-    let bufs = &mut bufs[remove..];
-    if let Some(first) = bufs.first_mut() {
-        let advance_by = n - accumulated_len;
-        let mut ptr = first.as_ptr();
-        let mut len = first.len();
-        unsafe {
-            ptr = ptr.add(advance_by);
-            len -= advance_by;
-            *first = IoSlice::<'a>::new(slice::from_raw_parts::<'a>(ptr, len));
+        match inner.write_vectored(&bufs[index..]) {
+            Ok(0) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "failed to write whole buffer",
+                ));
+            }
+            Ok(n) => offset = n,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
         }
     }
-    bufs
 }
 
 impl WriteLayered for std::io::Cursor<Vec<u8>> {
@@ -159,6 +166,20 @@ impl WriteLayered for std::io::Cursor<&mut [u8]> {
     }
 }
 
+impl WriteLayered for std::io::Sink {
+    #[inline]
+    fn close(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl WriteLayered for Vec<u8> {
+    #[inline]
+    fn close(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 impl<W: WriteLayered> WriteLayered for Box<W> {
     #[inline]
     fn close(&mut self) -> io::Result<()> {
@@ -172,3 +193,90 @@ impl<W: WriteLayered> WriteLayered for &mut W {
         (**self).close()
     }
 }
+
+#[test]
+fn test_sink_write_layered() {
+    let mut sink = io::sink();
+    assert_eq!(sink.write(b"hello").unwrap(), 5);
+    sink.flush_with_status(Status::push()).unwrap();
+    sink.write_all(b"world").unwrap();
+    sink.close().unwrap();
+    sink.abandon();
+}
+
+#[test]
+fn test_write_all_with_status() {
+    use crate::BufWriterLayered;
+
+    let mut writer = BufWriterLayered::new(std::io::Cursor::new(Vec::new()));
+    writer
+        .write_all_with_status(b"hello", Status::active())
+        .unwrap();
+    assert!(writer.get_ref().get_ref().is_empty());
+    writer
+        .write_all_with_status(b" world", Status::push())
+        .unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"hello world");
+    writer.write_all_with_status(b"!", Status::End).unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"hello world!");
+}
+
+#[test]
+fn test_vec_write_layered() {
+    let mut v = Vec::new();
+    v.write_all_with_status(b"hello", Status::push()).unwrap();
+    v.write_all_with_status(b" world", Status::End).unwrap();
+    assert_eq!(v, b"hello world");
+    assert_eq!(v.suggested_buffer_size(), 0);
+}
+
+#[test]
+fn test_default_write_all_vectored() {
+    // A writer which writes at most 3 bytes at a time.
+    struct Trickle(Vec<u8>);
+
+    impl Write for Trickle {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let size = buf.len().min(3);
+            self.0.extend_from_slice(&buf[..size]);
+            Ok(size)
+        }
+
+        fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+            match bufs.iter().find(|buf| !buf.is_empty()) {
+                Some(buf) => self.write(buf),
+                None => Ok(0),
+            }
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut writer = Trickle(Vec::new());
+    let mut bufs = [
+        IoSlice::new(b"hello"),
+        IoSlice::new(b""),
+        IoSlice::new(b" "),
+        IoSlice::new(b"world!"),
+    ];
+    default_write_all_vectored(&mut writer, &mut bufs).unwrap();
+    assert_eq!(writer.0, b"hello world!");
+
+    let mut bufs = [IoSlice::new(b""), IoSlice::new(b"")];
+    default_write_all_vectored(&mut writer, &mut bufs).unwrap();
+    assert_eq!(writer.0, b"hello world!");
+
+    let mut bufs = [IoSlice::new(b"full")];
+    let err = default_write_all_vectored(&mut &mut [0_u8; 2][..], &mut bufs).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+}
+
+#[test]
+fn test_default_is_vectored() {
+    use crate::{default_is_read_vectored, SliceReader};
+
+    assert!(!default_is_read_vectored(&SliceReader::new(b"hello")));
+    assert!(!default_is_write_vectored(&io::sink()));
+}